@@ -0,0 +1,425 @@
+#![deny(missing_docs)]
+
+/*!
+Derive macros for [`kai`](https://docs.rs/kai)
+
+These are reexported by `kai` when its `derive` feature is enabled.
+*/
+
+extern crate proc_macro;
+
+use std::collections::HashMap;
+
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse_macro_input, spanned::Spanned, Data, DeriveInput, Expr, Field, Fields, Index, Variant,
+};
+
+/**
+Derives `kai::close::Close` for a struct
+
+Fields whose types mention `f32` or `f64`, such as `f64`, `Vec<f32>`, or `Option<[f64; 3]>`,
+are compared with `Close`. All other fields are compared with `PartialEq`.
+
+# Field attributes
+* `#[close(approx)]` Compare the field with `Close` even though its type does not mention a float
+* `#[close(eq)]` Compare the field with `PartialEq` even though its type mentions a float
+* `#[close(skip)]` Do not compare the field at all
+* `#[close(abs = 1e-6)]` Compare the field with `Close` using an absolute tolerance
+* `#[close(rel = 1e-9)]` Compare the field with `Close` using a relative tolerance
+* `#[close(ulps = 4)]` Compare the field with `Close` using a ULP tolerance
+* `#[close(tolerance = expr)]` Compare the field with `Close` using any `Tolerance` expression
+
+A field with its own tolerance ignores the tolerance passed to `Close::close_with`.
+
+For generic structs, each compared field whose type mentions a type parameter adds a
+`Close` or `PartialEq` bound on that field type to the implementation.
+*/
+#[proc_macro_derive(Close, attributes(close))]
+pub fn derive_close(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    close_impl(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+enum Compare {
+    Approx(Option<TokenStream>),
+    Eq,
+    Skip,
+}
+
+fn close_impl(input: DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "`Close` can only be derived for structs",
+            ))
+        }
+    };
+    let type_params: Vec<String> = input
+        .generics
+        .type_params()
+        .map(|param| param.ident.to_string())
+        .collect();
+    let mut generics = input.generics.clone();
+    let mut close_with = Vec::new();
+    let mut mismatches = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        let ty = &field.ty;
+        let generic = mentions_ident(ty.to_token_stream(), |name| {
+            type_params.iter().any(|param| name == param)
+        });
+        let (member, name) = match &field.ident {
+            Some(ident) => (ident.to_token_stream(), ident.to_string()),
+            None => (Index::from(i).to_token_stream(), i.to_string()),
+        };
+        match field_compare(field)? {
+            Compare::Approx(tolerance) => {
+                if generic {
+                    generics
+                        .make_where_clause()
+                        .predicates
+                        .push(syn::parse_quote!(#ty: ::kai::close::Close));
+                }
+                let tolerance = tolerance.unwrap_or_else(|| quote!(tolerance));
+                close_with.push(quote! {
+                    ::kai::close::Close::close_with(&self.#member, &other.#member, #tolerance)
+                });
+                mismatches.push(quote! {
+                    ::kai::close::Close::mismatches(
+                        &self.#member,
+                        &other.#member,
+                        #tolerance,
+                        &format!("{}.{}", path, #name),
+                        out,
+                    );
+                });
+            }
+            Compare::Eq => {
+                if generic {
+                    generics
+                        .make_where_clause()
+                        .predicates
+                        .push(syn::parse_quote!(#ty: ::core::cmp::PartialEq));
+                }
+                close_with.push(quote!(self.#member == other.#member));
+                mismatches.push(quote! {
+                    if self.#member != other.#member {
+                        out.push(::kai::close::Mismatch::Values {
+                            path: format!("{}.{}", path, #name),
+                        });
+                    }
+                });
+            }
+            Compare::Skip => {}
+        }
+    }
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::kai::close::Close for #ident #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn close_with(&self, other: &Self, tolerance: ::kai::close::Tolerance) -> bool {
+                true #(&& #close_with)*
+            }
+            #[allow(unused_variables)]
+            fn mismatches(
+                &self,
+                other: &Self,
+                tolerance: ::kai::close::Tolerance,
+                path: &str,
+                out: &mut Vec<::kai::close::Mismatch>,
+            ) {
+                #(#mismatches)*
+            }
+        }
+    })
+}
+
+fn field_compare(field: &Field) -> syn::Result<Compare> {
+    let mut compare = None;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("close"))
+    {
+        attr.parse_nested_meta(|meta| {
+            let tolerance = |variant: TokenStream| -> syn::Result<Compare> {
+                let value: Expr = meta.value()?.parse()?;
+                Ok(Compare::Approx(Some(
+                    quote!(::kai::close::Tolerance::#variant(#value as _)),
+                )))
+            };
+            let new = if meta.path.is_ident("approx") {
+                Compare::Approx(None)
+            } else if meta.path.is_ident("eq") {
+                Compare::Eq
+            } else if meta.path.is_ident("skip") {
+                Compare::Skip
+            } else if meta.path.is_ident("abs") {
+                tolerance(quote!(Absolute))?
+            } else if meta.path.is_ident("rel") {
+                tolerance(quote!(Relative))?
+            } else if meta.path.is_ident("ulps") {
+                tolerance(quote!(Ulps))?
+            } else if meta.path.is_ident("tolerance") {
+                let value: Expr = meta.value()?.parse()?;
+                Compare::Approx(Some(value.to_token_stream()))
+            } else {
+                return Err(meta.error("unknown `close` attribute"));
+            };
+            if compare.replace(new).is_some() {
+                return Err(meta.error("only one `close` attribute is allowed per field"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(compare.unwrap_or_else(|| {
+        if mentions_float(field.ty.to_token_stream()) {
+            Compare::Approx(None)
+        } else {
+            Compare::Eq
+        }
+    }))
+}
+
+fn mentions_float(tokens: TokenStream) -> bool {
+    mentions_ident(tokens, |ident| ident == "f32" || ident == "f64")
+}
+
+/**
+Derives accessors for each variant of an enum
+
+For a variant `Baz`, these methods are generated:
+* `is_baz(&self) -> bool`
+* `as_baz(&self) -> Option<&T>`
+* `as_baz_mut(&mut self) -> Option<&mut T>`
+* `into_baz(self) -> Result<T, Self>`
+
+`T` is the variant's field type. If the variant has several fields, tuple or named,
+`T` is a tuple of them in declaration order. Unit variants only get `is_baz`.
+
+`TryFrom<Enum>` is also implemented for each variant's `T`, unless more than one
+variant has the same `T` or `T` mentions one of the enum's type parameters.
+
+Two variants cannot generate methods with the same name. For example, `Ref(i32)` and
+`RefMut(u8)` would both generate `as_ref_mut`, so deriving `Variants` fails with an
+error on the second variant that names the clashing method. Rename one of the variants to fix it.
+
+# Attributes
+* `#[variants(no_try_from)]` on the enum: Do not implement `TryFrom` for any variant
+* `#[variants(no_try_from)]` on a variant: Do not implement `TryFrom` for that variant
+*/
+#[proc_macro_derive(Variants, attributes(variants))]
+pub fn derive_variants(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    variants_impl(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn variants_impl(input: DeriveInput) -> syn::Result<TokenStream> {
+    let variants = match &input.data {
+        Data::Enum(data) => &data.variants,
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "`Variants` can only be derived for enums",
+            ))
+        }
+    };
+    let ident = &input.ident;
+    let vis = &input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let type_params: Vec<String> = input
+        .generics
+        .type_params()
+        .map(|param| param.ident.to_string())
+        .collect();
+    let no_try_from = variants_no_try_from(&input.attrs)?;
+    let mut methods = Vec::new();
+    let mut conversions = Vec::new();
+    let mut names = HashMap::new();
+    for variant in variants {
+        let name = &variant.ident;
+        let variant_no_try_from = no_try_from || variants_no_try_from(&variant.attrs)?;
+        let snake = snake_case(&name.to_string());
+        let is = format_ident!("is_{}", snake);
+        claim_method(&mut names, &is, variant)?;
+        let is_doc = format!("Check if this is a `{}`", name);
+        let pattern = match &variant.fields {
+            Fields::Named(_) => quote!(Self::#name { .. }),
+            Fields::Unnamed(_) => quote!(Self::#name(..)),
+            Fields::Unit => quote!(Self::#name),
+        };
+        methods.push(quote! {
+            #[doc = #is_doc]
+            #vis fn #is(&self) -> bool {
+                matches!(self, #pattern)
+            }
+        });
+        if variant.fields.is_empty() {
+            continue;
+        }
+        let bindings: Vec<_> = (0..variant.fields.len())
+            .map(|i| format_ident!("field{}", i))
+            .collect();
+        let pattern = match &variant.fields {
+            Fields::Named(fields) => {
+                let names = fields.named.iter().map(|field| &field.ident);
+                quote!(Self::#name { #(#names: #bindings),* })
+            }
+            _ => quote!(Self::#name(#(#bindings),*)),
+        };
+        let types: Vec<_> = variant.fields.iter().map(|field| &field.ty).collect();
+        let (ty, ref_ty, mut_ty, values) = if types.len() == 1 {
+            let ty = types[0];
+            let binding = &bindings[0];
+            (
+                quote!(#ty),
+                quote!(&#ty),
+                quote!(&mut #ty),
+                quote!(#binding),
+            )
+        } else {
+            (
+                quote!((#(#types),*)),
+                quote!((#(&#types),*)),
+                quote!((#(&mut #types),*)),
+                quote!((#(#bindings),*)),
+            )
+        };
+        let as_ref = format_ident!("as_{}", snake);
+        let as_mut = format_ident!("as_{}_mut", snake);
+        let into = format_ident!("into_{}", snake);
+        for method in [&as_ref, &as_mut, &into] {
+            claim_method(&mut names, method, variant)?;
+        }
+        let as_ref_doc = format!("Get a reference to the contents of a `{}`", name);
+        let as_mut_doc = format!("Get a mutable reference to the contents of a `{}`", name);
+        let into_doc = format!(
+            "Get the contents of a `{}`, or the value back if it is a different variant",
+            name
+        );
+        methods.push(quote! {
+            #[doc = #as_ref_doc]
+            #[allow(unreachable_patterns)]
+            #vis fn #as_ref(&self) -> Option<#ref_ty> {
+                match self {
+                    #pattern => Some(#values),
+                    _ => None,
+                }
+            }
+            #[doc = #as_mut_doc]
+            #[allow(unreachable_patterns)]
+            #vis fn #as_mut(&mut self) -> Option<#mut_ty> {
+                match self {
+                    #pattern => Some(#values),
+                    _ => None,
+                }
+            }
+            #[doc = #into_doc]
+            #[allow(unreachable_patterns)]
+            #vis fn #into(self) -> Result<#ty, Self> {
+                match self {
+                    #pattern => Ok(#values),
+                    other => Err(other),
+                }
+            }
+        });
+        conversions.push((ty, into, variant_no_try_from));
+    }
+    // Only implement `TryFrom` where it cannot conflict with another variant or violate the orphan rules
+    let conversions = conversions.iter().filter(|(ty, _, no_try_from)| {
+        let key = ty.to_string();
+        !no_try_from
+            && conversions
+                .iter()
+                .filter(|(other, _, _)| other.to_string() == key)
+                .count()
+                == 1
+            && !mentions_ident(ty.clone(), |name| {
+                name == ident || type_params.iter().any(|param| name == param)
+            })
+    });
+    let conversions = conversions.map(|(ty, into, _)| {
+        quote! {
+            impl #impl_generics ::core::convert::TryFrom<#ident #ty_generics> for #ty #where_clause {
+                type Error = #ident #ty_generics;
+                fn try_from(value: #ident #ty_generics) -> Result<Self, Self::Error> {
+                    value.#into()
+                }
+            }
+        }
+    });
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #(#methods)*
+        }
+        #(#conversions)*
+    })
+}
+
+/// Record the variant that generates a method, failing if another variant already generates it
+fn claim_method(
+    names: &mut HashMap<String, Ident>,
+    method: &Ident,
+    variant: &Variant,
+) -> syn::Result<()> {
+    match names.insert(method.to_string(), variant.ident.clone()) {
+        Some(other) => Err(syn::Error::new(
+            variant.ident.span(),
+            format!(
+                "variants `{}` and `{}` both generate a method named `{}`",
+                other, variant.ident, method
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+fn variants_no_try_from(attrs: &[syn::Attribute]) -> syn::Result<bool> {
+    let mut no_try_from = false;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("variants")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("no_try_from") {
+                no_try_from = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown `variants` attribute"))
+            }
+        })?;
+    }
+    Ok(no_try_from)
+}
+
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_is_lower) {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+fn mentions_ident<F>(tokens: TokenStream, f: F) -> bool
+where
+    F: Fn(&proc_macro2::Ident) -> bool + Copy,
+{
+    tokens.into_iter().any(|tt| match tt {
+        TokenTree::Ident(ident) => f(&ident),
+        TokenTree::Group(group) => mentions_ident(group.stream(), f),
+        _ => false,
+    })
+}
@@ -120,3 +120,16 @@ where
         &self.temp
     }
 }
+
+#[cfg(feature = "serde")]
+impl<'a, T> serde::Serialize for Adapter<'a, T>
+where
+    T: FromStr + Display + serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        T::serialize(&self.temp, serializer)
+    }
+}
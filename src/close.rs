@@ -0,0 +1,612 @@
+/*!
+Functions for checking if two floating-point numbers are close enough to be considered equal
+
+The simple functions use the `std::f**::EPSILON` constants to check if two numbers are close
+enough for their difference to be the result of rounding errors. I made these primarily to
+get clippy off my back about directly comparing floats.
+
+Comparing against `EPSILON` only makes sense for numbers with a magnitude around `1`.
+For other numbers, use the absolute, relative, or ULP comparisons, or the [`Close`](trait.Close.html)
+trait with a [`Tolerance`](enum.Tolerance.html).
+
+# Example
+```
+use kai::*;
+
+// Large numbers are almost never within `EPSILON` of each other
+assert!(!close::f64(1e10 + 1e-5, 1e10));
+assert!(close::f64_rel(1e10 + 1e-5, 1e10, 1e-12));
+
+// Relative comparisons fail near zero
+assert!(!close::f64_rel(1e-20, 0.0, 1e-12));
+assert!(close::f64_abs(1e-20, 0.0, 1e-12));
+
+// Numbers that differ only by their last bit are 1 ULP apart
+let a = 1.0f32;
+let b = f32::from_bits(a.to_bits() + 1);
+assert_eq!(Some(1), close::ulps_f32(a, b));
+assert!(close::f32_ulps(a, b, 1));
+
+// Whole structures can be compared with the `Close` trait
+let v = vec![(0.1 + 0.2, Some(1.0)), (0.5, None)];
+let w = vec![(0.3, Some(1.0)), (0.5, None)];
+assert!(v.close(&w));
+assert!(!v.close(&vec![(0.3, None), (0.5, None)]));
+```
+*/
+
+#![allow(clippy::trivially_copy_pass_by_ref)]
+
+/// Check if two `f32`s are close enough to be considered equal
+pub fn f32(a: f32, b: f32) -> bool {
+    (a - b).abs() < f32::EPSILON
+}
+/// Check if two `&f32`s are close enough to be considered equal
+pub fn f32_ref(a: &f32, b: &f32) -> bool {
+    (*a - *b).abs() < f32::EPSILON
+}
+/// Check if two `f64`s are close enough to be considered equal
+pub fn f64(a: f64, b: f64) -> bool {
+    (a - b).abs() < f64::EPSILON
+}
+/// Check if two `&f64`s are close enough to be considered equal
+pub fn f64_ref(a: &f64, b: &f64) -> bool {
+    (*a - *b).abs() < f64::EPSILON
+}
+
+/// Check if two `f32`s differ by at most `tolerance`
+pub fn f32_abs(a: f32, b: f32, tolerance: f32) -> bool {
+    a == b || (a - b).abs() <= tolerance
+}
+/// Check if two `f64`s differ by at most `tolerance`
+pub fn f64_abs(a: f64, b: f64, tolerance: f64) -> bool {
+    a == b || (a - b).abs() <= tolerance
+}
+
+/// Check if two `f32`s differ by at most `tolerance` times the larger of their magnitudes
+pub fn f32_rel(a: f32, b: f32, tolerance: f32) -> bool {
+    a == b || (a - b).abs() <= tolerance * a.abs().max(b.abs())
+}
+/// Check if two `f64`s differ by at most `tolerance` times the larger of their magnitudes
+pub fn f64_rel(a: f64, b: f64, tolerance: f64) -> bool {
+    a == b || (a - b).abs() <= tolerance * a.abs().max(b.abs())
+}
+
+/// Check if two `f32`s are at most `max_ulps` representable numbers apart
+pub fn f32_ulps(a: f32, b: f32, max_ulps: u32) -> bool {
+    ulps_f32(a, b).is_some_and(|ulps| ulps <= max_ulps)
+}
+/// Check if two `f64`s are at most `max_ulps` representable numbers apart
+pub fn f64_ulps(a: f64, b: f64, max_ulps: u64) -> bool {
+    ulps_f64(a, b).is_some_and(|ulps| ulps <= max_ulps)
+}
+
+/**
+Get the number of representable `f32`s between two `f32`s
+
+`0.0` and `-0.0` are considered equal. Returns `None` if either number is NaN.
+*/
+pub fn ulps_f32(a: f32, b: f32) -> Option<u32> {
+    fn key(x: f32) -> i64 {
+        let bits = x.to_bits();
+        let magnitude = i64::from(bits & 0x7fff_ffff);
+        if bits >> 31 == 1 {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+    if a.is_nan() || b.is_nan() {
+        None
+    } else {
+        Some((key(a) - key(b)).unsigned_abs() as u32)
+    }
+}
+/**
+Get the number of representable `f64`s between two `f64`s
+
+`0.0` and `-0.0` are considered equal. Returns `None` if either number is NaN.
+*/
+pub fn ulps_f64(a: f64, b: f64) -> Option<u64> {
+    fn key(x: f64) -> i128 {
+        let bits = x.to_bits();
+        let magnitude = i128::from(bits & 0x7fff_ffff_ffff_ffff);
+        if bits >> 63 == 1 {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+    if a.is_nan() || b.is_nan() {
+        None
+    } else {
+        Some((key(a) - key(b)).unsigned_abs() as u64)
+    }
+}
+
+/**
+A strategy for deciding if two floating-point numbers are close enough to be considered equal
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Tolerance {
+    /// The numbers differ by less than the `EPSILON` constant of their type
+    #[default]
+    Epsilon,
+    /// The numbers differ by at most the given amount
+    Absolute(f64),
+    /// The numbers differ by at most the given fraction of the larger of their magnitudes
+    Relative(f64),
+    /// The numbers are at most the given number of representable numbers apart
+    Ulps(u64),
+}
+
+impl Tolerance {
+    /// Check if two `f32`s are close according to this tolerance
+    pub fn f32(self, a: f32, b: f32) -> bool {
+        match self {
+            Tolerance::Epsilon => f32(a, b),
+            Tolerance::Absolute(tolerance) => f32_abs(a, b, tolerance as f32),
+            Tolerance::Relative(tolerance) => f32_rel(a, b, tolerance as f32),
+            Tolerance::Ulps(max_ulps) => {
+                ulps_f32(a, b).is_some_and(|ulps| u64::from(ulps) <= max_ulps)
+            }
+        }
+    }
+    /// Check if two `f64`s are close according to this tolerance
+    pub fn f64(self, a: f64, b: f64) -> bool {
+        match self {
+            Tolerance::Epsilon => f64(a, b),
+            Tolerance::Absolute(tolerance) => f64_abs(a, b, tolerance),
+            Tolerance::Relative(tolerance) => f64_rel(a, b, tolerance),
+            Tolerance::Ulps(max_ulps) => f64_ulps(a, b, max_ulps),
+        }
+    }
+}
+
+/**
+Checks if two values made of floating-point numbers are close enough to be considered equal
+
+This is implemented for `f32`, `f64`, and references, tuples, arrays, slices,
+`Vec`s, and `Option`s of types that implement `Close`.
+
+Collections are only close if they have the same length and all of their elements are close.
+`Option`s are close if they are both `None` or if they are both `Some` and their values are close.
+
+# Example
+```
+use kai::*;
+
+assert!([1.0, 2.0].close(&[1.0, 2.0 + 1e-17]));
+assert!((1e9f32, 2.0f64).close_with(&(1e9 + 50.0, 2.0), Tolerance::Relative(1e-6)));
+assert!(Some(&0.0).close_with(&Some(&1e-300), Tolerance::Absolute(1e-200)));
+```
+*/
+pub trait Close {
+    /// Check if two values are close according to the tolerance
+    fn close_with(&self, other: &Self, tolerance: Tolerance) -> bool;
+    /// Check if two values differ by less than the `EPSILON` constant of their type
+    fn close(&self, other: &Self) -> bool {
+        self.close_with(other, Tolerance::Epsilon)
+    }
+    /**
+    Collect the parts of two values that are not close according to the tolerance
+
+    `path` is the location of these values within the outermost values being compared.
+    This is used by [`assert_close!`](../macro.assert_close.html) to report failures.
+    The default implementation reports the whole value as a single mismatch.
+    */
+    fn mismatches(&self, other: &Self, tolerance: Tolerance, path: &str, out: &mut Vec<Mismatch>) {
+        if !self.close_with(other, tolerance) {
+            out.push(Mismatch::Values { path: path.into() });
+        }
+    }
+}
+
+/**
+A part of two values that are not close, as reported by [`Close::mismatches`](trait.Close.html#method.mismatches)
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mismatch {
+    /// Two numbers are not close
+    Numbers {
+        /// The location of the numbers
+        path: String,
+        /// The left number
+        left: f64,
+        /// The right number
+        right: f64,
+        /// The ULP distance between the numbers, measured in their original type
+        ulps: Option<u64>,
+        /// The `EPSILON` constant of the numbers' original type
+        epsilon: f64,
+    },
+    /// Two collections have different lengths
+    Lengths {
+        /// The location of the collections
+        path: String,
+        /// The length of the left collection
+        left: usize,
+        /// The length of the right collection
+        right: usize,
+    },
+    /// Two values that are not numbers or collections are not close
+    Values {
+        /// The location of the values
+        path: String,
+    },
+}
+
+impl Mismatch {
+    /// Get the location of the mismatch
+    pub fn path(&self) -> &str {
+        match self {
+            Mismatch::Numbers { path, .. }
+            | Mismatch::Lengths { path, .. }
+            | Mismatch::Values { path } => path,
+        }
+    }
+}
+
+impl std::fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let path = if self.path().is_empty() {
+            "value"
+        } else {
+            self.path()
+        };
+        match self {
+            Mismatch::Numbers {
+                left, right, ulps, ..
+            } => {
+                let diff = (left - right).abs();
+                write!(
+                    f,
+                    "{}: left = {:?}, right = {:?}, absolute difference = {:?}, \
+                     relative difference = {:?}, ULP distance = ",
+                    path,
+                    left,
+                    right,
+                    diff,
+                    diff / left.abs().max(right.abs())
+                )?;
+                match ulps {
+                    Some(ulps) => write!(f, "{}", ulps),
+                    None => write!(f, "NaN"),
+                }
+            }
+            Mismatch::Lengths { left, right, .. } => write!(
+                f,
+                "{}: left length = {}, right length = {}",
+                path, left, right
+            ),
+            Mismatch::Values { .. } => write!(f, "{}: values differ", path),
+        }
+    }
+}
+
+impl Close for f32 {
+    fn close_with(&self, other: &Self, tolerance: Tolerance) -> bool {
+        tolerance.f32(*self, *other)
+    }
+    fn mismatches(&self, other: &Self, tolerance: Tolerance, path: &str, out: &mut Vec<Mismatch>) {
+        if !self.close_with(other, tolerance) {
+            out.push(Mismatch::Numbers {
+                path: path.into(),
+                left: f64::from(*self),
+                right: f64::from(*other),
+                ulps: ulps_f32(*self, *other).map(u64::from),
+                epsilon: f64::from(f32::EPSILON),
+            });
+        }
+    }
+}
+
+impl Close for f64 {
+    fn close_with(&self, other: &Self, tolerance: Tolerance) -> bool {
+        tolerance.f64(*self, *other)
+    }
+    fn mismatches(&self, other: &Self, tolerance: Tolerance, path: &str, out: &mut Vec<Mismatch>) {
+        if !self.close_with(other, tolerance) {
+            out.push(Mismatch::Numbers {
+                path: path.into(),
+                left: *self,
+                right: *other,
+                ulps: ulps_f64(*self, *other),
+                epsilon: f64::EPSILON,
+            });
+        }
+    }
+}
+
+impl<T> Close for &T
+where
+    T: Close + ?Sized,
+{
+    fn close_with(&self, other: &Self, tolerance: Tolerance) -> bool {
+        T::close_with(self, other, tolerance)
+    }
+    fn mismatches(&self, other: &Self, tolerance: Tolerance, path: &str, out: &mut Vec<Mismatch>) {
+        T::mismatches(self, other, tolerance, path, out)
+    }
+}
+
+impl<T> Close for [T]
+where
+    T: Close,
+{
+    fn close_with(&self, other: &Self, tolerance: Tolerance) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .zip(other)
+                .all(|(a, b)| a.close_with(b, tolerance))
+    }
+    fn mismatches(&self, other: &Self, tolerance: Tolerance, path: &str, out: &mut Vec<Mismatch>) {
+        if self.len() != other.len() {
+            out.push(Mismatch::Lengths {
+                path: path.into(),
+                left: self.len(),
+                right: other.len(),
+            });
+            return;
+        }
+        for (i, (a, b)) in self.iter().zip(other).enumerate() {
+            a.mismatches(b, tolerance, &format!("{}[{}]", path, i), out);
+        }
+    }
+}
+
+impl<T, const N: usize> Close for [T; N]
+where
+    T: Close,
+{
+    fn close_with(&self, other: &Self, tolerance: Tolerance) -> bool {
+        self[..].close_with(&other[..], tolerance)
+    }
+    fn mismatches(&self, other: &Self, tolerance: Tolerance, path: &str, out: &mut Vec<Mismatch>) {
+        self[..].mismatches(&other[..], tolerance, path, out)
+    }
+}
+
+impl<T> Close for Vec<T>
+where
+    T: Close,
+{
+    fn close_with(&self, other: &Self, tolerance: Tolerance) -> bool {
+        self[..].close_with(&other[..], tolerance)
+    }
+    fn mismatches(&self, other: &Self, tolerance: Tolerance, path: &str, out: &mut Vec<Mismatch>) {
+        self[..].mismatches(&other[..], tolerance, path, out)
+    }
+}
+
+impl<T> Close for Option<T>
+where
+    T: Close,
+{
+    fn close_with(&self, other: &Self, tolerance: Tolerance) -> bool {
+        match (self, other) {
+            (Some(a), Some(b)) => a.close_with(b, tolerance),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+    fn mismatches(&self, other: &Self, tolerance: Tolerance, path: &str, out: &mut Vec<Mismatch>) {
+        match (self, other) {
+            (Some(a), Some(b)) => a.mismatches(b, tolerance, path, out),
+            (None, None) => {}
+            _ => out.push(Mismatch::Values { path: path.into() }),
+        }
+    }
+}
+
+macro_rules! close_tuple {
+    ($($T:ident $i:tt),*) => {
+        impl<$($T),*> Close for ($($T,)*)
+        where
+            $($T: Close,)*
+        {
+            fn close_with(&self, other: &Self, tolerance: Tolerance) -> bool {
+                $(self.$i.close_with(&other.$i, tolerance))&&*
+            }
+            fn mismatches(&self, other: &Self, tolerance: Tolerance, path: &str, out: &mut Vec<Mismatch>) {
+                $(self.$i.mismatches(&other.$i, tolerance, &format!("{}.{}", path, $i), out);)*
+            }
+        }
+    };
+}
+
+close_tuple!(A 0);
+close_tuple!(A 0, B 1);
+close_tuple!(A 0, B 1, C 2);
+close_tuple!(A 0, B 1, C 2, D 3);
+close_tuple!(A 0, B 1, C 2, D 3, E 4);
+close_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+close_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+close_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+/// The maximum number of mismatches reported by a failed [`assert_close!`](../macro.assert_close.html)
+const MAX_REPORTED_MISMATCHES: usize = 5;
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_close_failed<T>(
+    left: &T,
+    right: &T,
+    tolerance: Tolerance,
+    args: Option<std::fmt::Arguments>,
+) -> !
+where
+    T: Close + std::fmt::Debug + ?Sized,
+{
+    use std::fmt::Write;
+    let mut mismatches = Vec::new();
+    left.mismatches(right, tolerance, "", &mut mismatches);
+    let mut message = String::from("assertion `left ≈ right` failed");
+    if let Some(args) = args {
+        let _ = write!(message, ": {}", args);
+    }
+    let _ = write!(
+        message,
+        "\n     left: {:?}\n    right: {:?}\ntolerance: {:?}",
+        left, right, tolerance
+    );
+    if let Tolerance::Epsilon = tolerance {
+        // Show the constants that were actually used for the mismatched numbers
+        let mut epsilons = Vec::new();
+        for mismatch in &mismatches {
+            if let Mismatch::Numbers { epsilon, .. } = mismatch {
+                if !epsilons.contains(epsilon) {
+                    epsilons.push(*epsilon);
+                }
+            }
+        }
+        if !epsilons.is_empty() {
+            let epsilons: Vec<_> = epsilons.iter().map(|e| format!("{:?}", e)).collect();
+            let _ = write!(message, " ({})", epsilons.join(", "));
+        }
+    }
+    if mismatches.len() > MAX_REPORTED_MISMATCHES {
+        let _ = write!(
+            message,
+            "\nfirst {} of {} mismatches:",
+            MAX_REPORTED_MISMATCHES,
+            mismatches.len()
+        );
+    } else {
+        let _ = write!(message, "\nmismatches:");
+    }
+    for mismatch in mismatches.iter().take(MAX_REPORTED_MISMATCHES) {
+        let _ = write!(message, "\n    {}", mismatch);
+    }
+    panic!("{}", message)
+}
+
+/**
+Asserts that two values are close enough to be considered equal
+
+The values must implement [`Close`](close/trait.Close.html) and `Debug`.
+If no [`Tolerance`](close/enum.Tolerance.html) is given, `Tolerance::Epsilon` is used.
+A custom panic message can be given after the tolerance.
+
+On failure, the panic message shows both values, the tolerance, and up to five mismatched
+numbers along with their absolute difference, relative difference, and ULP distance.
+With `Tolerance::Epsilon`, the `EPSILON` constants of the mismatched numbers' types are shown as well.
+
+# Syntax
+```ignore
+assert_close!( left, right )
+assert_close!( left, right, tolerance )
+assert_close!( left, right, tolerance, format, args... )
+```
+
+# Example
+```
+use kai::*;
+
+assert_close!(0.1 + 0.2, 0.3);
+assert_close!(
+    vec![1e9, 2e9],
+    vec![1e9 + 1.0, 2e9],
+    Tolerance::Relative(1e-6),
+    "positions diverged"
+);
+```
+
+```should_panic
+use kai::*;
+
+// Panics with a message like:
+//
+// assertion `left ≈ right` failed
+//      left: [1.0, 2.0, 3.0]
+//     right: [1.0, 2.5, 3.0]
+// tolerance: Absolute(0.1)
+// mismatches:
+//     [1]: left = 2.0, right = 2.5, absolute difference = 0.5, relative difference = 0.2, ULP distance = 1125899906842624
+assert_close!([1.0, 2.0, 3.0], [1.0, 2.5, 3.0], Tolerance::Absolute(0.1));
+```
+*/
+#[macro_export]
+macro_rules! assert_close {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_close!($left, $right, $crate::close::Tolerance::Epsilon)
+    };
+    ($left:expr, $right:expr, $tolerance:expr $(,)?) => {
+        match (&$left, &$right, $tolerance) {
+            (left, right, tolerance) => {
+                if !$crate::close::Close::close_with(left, right, tolerance) {
+                    $crate::close::assert_close_failed(left, right, tolerance, None);
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $tolerance:expr, $($arg:tt)+) => {
+        match (&$left, &$right, $tolerance) {
+            (left, right, tolerance) => {
+                if !$crate::close::Close::close_with(left, right, tolerance) {
+                    $crate::close::assert_close_failed(
+                        left,
+                        right,
+                        tolerance,
+                        Some(format_args!($($arg)+)),
+                    );
+                }
+            }
+        }
+    };
+}
+
+/**
+Asserts that two values are close enough to be considered equal using a relative tolerance
+
+This is shorthand for [`assert_close!`](macro.assert_close.html) with `Tolerance::Relative`.
+
+# Syntax
+```ignore
+assert_close_rel!( left, right, tolerance )
+assert_close_rel!( left, right, tolerance, format, args... )
+```
+
+# Example
+```
+use kai::*;
+
+assert_close_rel!(1e10 + 1.0, 1e10, 1e-9);
+```
+*/
+#[macro_export]
+macro_rules! assert_close_rel {
+    ($left:expr, $right:expr, $tolerance:expr $(,)?) => {
+        $crate::assert_close!($left, $right, $crate::close::Tolerance::Relative($tolerance))
+    };
+    ($left:expr, $right:expr, $tolerance:expr, $($arg:tt)+) => {
+        $crate::assert_close!(
+            $left,
+            $right,
+            $crate::close::Tolerance::Relative($tolerance),
+            $($arg)+
+        )
+    };
+}
+
+/**
+Asserts that two values are close enough to be considered equal, but only in debug builds
+
+This takes the same arguments as [`assert_close!`](macro.assert_close.html).
+
+# Example
+```
+use kai::*;
+
+debug_assert_close!(0.1 + 0.2, 0.3);
+```
+*/
+#[macro_export]
+macro_rules! debug_assert_close {
+    ($($arg:tt)*) => {
+        if cfg!(debug_assertions) {
+            $crate::assert_close!($($arg)*);
+        }
+    };
+}
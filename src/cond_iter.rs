@@ -0,0 +1,877 @@
+use super::*;
+
+/**
+An iterator adaptor created by [`KaiIterator::chain_if`](trait.KaiIterator.html#method.chain_if)
+*/
+pub enum ChainIf<I, J>
+where
+    I: IntoIterator,
+    J: IntoIterator<Item = I::Item>,
+{
+    /// The iterator was chained
+    Chained(I::IntoIter, J::IntoIter),
+    /// The iterator was not chained
+    NotChained(I::IntoIter),
+}
+
+impl<I, J> Iterator for ChainIf<I, J>
+where
+    I: IntoIterator,
+    J: IntoIterator<Item = I::Item>,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        use ChainIf::*;
+        match self {
+            Chained(first, second) => first.next().or_else(|| second.next()),
+            NotChained(iter) => iter.next(),
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        use ChainIf::*;
+        match self {
+            Chained(first, second) => chain_size_hint(first.size_hint(), second.size_hint()),
+            NotChained(iter) => iter.size_hint(),
+        }
+    }
+    fn count(self) -> usize {
+        use ChainIf::*;
+        match self {
+            Chained(first, second) => first.count() + second.count(),
+            NotChained(iter) => iter.count(),
+        }
+    }
+    fn last(self) -> Option<Self::Item> {
+        use ChainIf::*;
+        match self {
+            Chained(first, second) => {
+                let first = first.last();
+                second.last().or(first)
+            }
+            NotChained(iter) => iter.last(),
+        }
+    }
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        use ChainIf::*;
+        match self {
+            Chained(first, second) => second.fold(first.fold(init, &mut f), f),
+            NotChained(iter) => iter.fold(init, f),
+        }
+    }
+}
+
+impl<I, J> DoubleEndedIterator for ChainIf<I, J>
+where
+    I: IntoIterator,
+    J: IntoIterator<Item = I::Item>,
+    I::IntoIter: DoubleEndedIterator,
+    J::IntoIter: DoubleEndedIterator,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        use ChainIf::*;
+        match self {
+            Chained(first, second) => second.next_back().or_else(|| first.next_back()),
+            NotChained(iter) => iter.next_back(),
+        }
+    }
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        use ChainIf::*;
+        match self {
+            Chained(first, second) => first.rfold(second.rfold(init, &mut f), f),
+            NotChained(iter) => iter.rfold(init, f),
+        }
+    }
+}
+
+impl<I, J> iter::FusedIterator for ChainIf<I, J>
+where
+    I: IntoIterator,
+    J: IntoIterator<Item = I::Item>,
+    I::IntoIter: iter::FusedIterator,
+    J::IntoIter: iter::FusedIterator,
+{
+}
+
+impl<I, J> Clone for ChainIf<I, J>
+where
+    I: IntoIterator,
+    J: IntoIterator<Item = I::Item>,
+    I::IntoIter: Clone,
+    J::IntoIter: Clone,
+{
+    fn clone(&self) -> Self {
+        use ChainIf::*;
+        match self {
+            Chained(first, second) => Chained(first.clone(), second.clone()),
+            NotChained(iter) => NotChained(iter.clone()),
+        }
+    }
+}
+
+impl<I, J> Debug for ChainIf<I, J>
+where
+    I: IntoIterator,
+    J: IntoIterator<Item = I::Item>,
+    I::IntoIter: Debug,
+    J::IntoIter: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        use ChainIf::*;
+        match self {
+            Chained(first, second) => f.debug_tuple("Chained").field(first).field(second).finish(),
+            NotChained(iter) => f.debug_tuple("NotChained").field(iter).finish(),
+        }
+    }
+}
+
+/**
+An iterator adaptor created by [`KaiIterator::chain_if_else`](trait.KaiIterator.html#method.chain_if_else)
+*/
+pub enum ChainIfElse<I, J, K>
+where
+    I: IntoIterator,
+    J: IntoIterator<Item = I::Item>,
+    K: IntoIterator<Item = I::Item>,
+{
+    /// The iterator was chained with the first iterator
+    If(I::IntoIter, J::IntoIter),
+    /// The iterator was chained with the second iterator
+    Else(I::IntoIter, K::IntoIter),
+}
+
+impl<I, J, K> Iterator for ChainIfElse<I, J, K>
+where
+    I: IntoIterator,
+    J: IntoIterator<Item = I::Item>,
+    K: IntoIterator<Item = I::Item>,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        use ChainIfElse::*;
+        match self {
+            If(first, second) => first.next().or_else(|| second.next()),
+            Else(first, second) => first.next().or_else(|| second.next()),
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        use ChainIfElse::*;
+        match self {
+            If(first, second) => chain_size_hint(first.size_hint(), second.size_hint()),
+            Else(first, second) => chain_size_hint(first.size_hint(), second.size_hint()),
+        }
+    }
+    fn count(self) -> usize {
+        use ChainIfElse::*;
+        match self {
+            If(first, second) => first.count() + second.count(),
+            Else(first, second) => first.count() + second.count(),
+        }
+    }
+    fn last(self) -> Option<Self::Item> {
+        use ChainIfElse::*;
+        match self {
+            If(first, second) => {
+                let first = first.last();
+                second.last().or(first)
+            }
+            Else(first, second) => {
+                let first = first.last();
+                second.last().or(first)
+            }
+        }
+    }
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        use ChainIfElse::*;
+        match self {
+            If(first, second) => second.fold(first.fold(init, &mut f), f),
+            Else(first, second) => second.fold(first.fold(init, &mut f), f),
+        }
+    }
+}
+
+impl<I, J, K> DoubleEndedIterator for ChainIfElse<I, J, K>
+where
+    I: IntoIterator,
+    J: IntoIterator<Item = I::Item>,
+    K: IntoIterator<Item = I::Item>,
+    I::IntoIter: DoubleEndedIterator,
+    J::IntoIter: DoubleEndedIterator,
+    K::IntoIter: DoubleEndedIterator,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        use ChainIfElse::*;
+        match self {
+            If(first, second) => second.next_back().or_else(|| first.next_back()),
+            Else(first, second) => second.next_back().or_else(|| first.next_back()),
+        }
+    }
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        use ChainIfElse::*;
+        match self {
+            If(first, second) => first.rfold(second.rfold(init, &mut f), f),
+            Else(first, second) => first.rfold(second.rfold(init, &mut f), f),
+        }
+    }
+}
+
+impl<I, J, K> iter::FusedIterator for ChainIfElse<I, J, K>
+where
+    I: IntoIterator,
+    J: IntoIterator<Item = I::Item>,
+    K: IntoIterator<Item = I::Item>,
+    I::IntoIter: iter::FusedIterator,
+    J::IntoIter: iter::FusedIterator,
+    K::IntoIter: iter::FusedIterator,
+{
+}
+
+impl<I, J, K> Clone for ChainIfElse<I, J, K>
+where
+    I: IntoIterator,
+    J: IntoIterator<Item = I::Item>,
+    K: IntoIterator<Item = I::Item>,
+    I::IntoIter: Clone,
+    J::IntoIter: Clone,
+    K::IntoIter: Clone,
+{
+    fn clone(&self) -> Self {
+        use ChainIfElse::*;
+        match self {
+            If(first, second) => If(first.clone(), second.clone()),
+            Else(first, second) => Else(first.clone(), second.clone()),
+        }
+    }
+}
+
+impl<I, J, K> Debug for ChainIfElse<I, J, K>
+where
+    I: IntoIterator,
+    J: IntoIterator<Item = I::Item>,
+    K: IntoIterator<Item = I::Item>,
+    I::IntoIter: Debug,
+    J::IntoIter: Debug,
+    K::IntoIter: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        use ChainIfElse::*;
+        match self {
+            If(first, second) => f.debug_tuple("If").field(first).field(second).finish(),
+            Else(first, second) => f.debug_tuple("Else").field(first).field(second).finish(),
+        }
+    }
+}
+
+/// Combine the size hints of two chained iterators
+fn chain_size_hint(
+    (first_low, first_high): (usize, Option<usize>),
+    (second_low, second_high): (usize, Option<usize>),
+) -> (usize, Option<usize>) {
+    let low = first_low.saturating_add(second_low);
+    let high = match (first_high, second_high) {
+        (Some(first), Some(second)) => first.checked_add(second),
+        _ => None,
+    };
+    (low, high)
+}
+
+/**
+An iterator adaptor created by [`KaiIterator::map_if`](trait.KaiIterator.html#method.map_if)
+*/
+pub enum MapIf<I, F>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> I::Item,
+{
+    /// The iterator is mapped
+    Mapped(I::IntoIter, F),
+    /// The iterator is not mapped
+    NotMapped(I::IntoIter),
+}
+
+impl<I, F> Iterator for MapIf<I, F>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> I::Item,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        use MapIf::*;
+        match self {
+            Mapped(iter, f) => iter.next().map(f),
+            NotMapped(iter) => iter.next(),
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        use MapIf::*;
+        match self {
+            Mapped(iter, _) | NotMapped(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl<I, F> DoubleEndedIterator for MapIf<I, F>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> I::Item,
+    I::IntoIter: DoubleEndedIterator,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        use MapIf::*;
+        match self {
+            Mapped(iter, f) => iter.next_back().map(f),
+            NotMapped(iter) => iter.next_back(),
+        }
+    }
+}
+
+impl<I, F> ExactSizeIterator for MapIf<I, F>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> I::Item,
+    I::IntoIter: ExactSizeIterator,
+{
+}
+
+impl<I, F> iter::FusedIterator for MapIf<I, F>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> I::Item,
+    I::IntoIter: iter::FusedIterator,
+{
+}
+
+impl<I, F> Clone for MapIf<I, F>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> I::Item + Clone,
+    I::IntoIter: Clone,
+{
+    fn clone(&self) -> Self {
+        use MapIf::*;
+        match self {
+            Mapped(iter, f) => Mapped(iter.clone(), f.clone()),
+            NotMapped(iter) => NotMapped(iter.clone()),
+        }
+    }
+}
+
+impl<I, F> Debug for MapIf<I, F>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> I::Item,
+    I::IntoIter: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        use MapIf::*;
+        match self {
+            Mapped(iter, _) => f.debug_tuple("Mapped").field(iter).finish(),
+            NotMapped(iter) => f.debug_tuple("NotMapped").field(iter).finish(),
+        }
+    }
+}
+
+/**
+An iterator adaptor created by [`KaiIterator::filter_if`](trait.KaiIterator.html#method.filter_if)
+*/
+pub enum FilterIf<I, P>
+where
+    I: IntoIterator,
+    P: FnMut(&I::Item) -> bool,
+{
+    /// The iterator is filtered
+    Filtered(I::IntoIter, P),
+    /// The iterator is not filtered
+    NotFiltered(I::IntoIter),
+}
+
+impl<I, P> Iterator for FilterIf<I, P>
+where
+    I: IntoIterator,
+    P: FnMut(&I::Item) -> bool,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        use FilterIf::*;
+        match self {
+            Filtered(iter, predicate) => iter.find(|item| predicate(item)),
+            NotFiltered(iter) => iter.next(),
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        use FilterIf::*;
+        match self {
+            Filtered(iter, _) => (0, iter.size_hint().1),
+            NotFiltered(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl<I, P> DoubleEndedIterator for FilterIf<I, P>
+where
+    I: IntoIterator,
+    P: FnMut(&I::Item) -> bool,
+    I::IntoIter: DoubleEndedIterator,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        use FilterIf::*;
+        match self {
+            Filtered(iter, predicate) => iter.rfind(|item| predicate(item)),
+            NotFiltered(iter) => iter.next_back(),
+        }
+    }
+}
+
+impl<I, P> iter::FusedIterator for FilterIf<I, P>
+where
+    I: IntoIterator,
+    P: FnMut(&I::Item) -> bool,
+    I::IntoIter: iter::FusedIterator,
+{
+}
+
+impl<I, P> Clone for FilterIf<I, P>
+where
+    I: IntoIterator,
+    P: FnMut(&I::Item) -> bool + Clone,
+    I::IntoIter: Clone,
+{
+    fn clone(&self) -> Self {
+        use FilterIf::*;
+        match self {
+            Filtered(iter, predicate) => Filtered(iter.clone(), predicate.clone()),
+            NotFiltered(iter) => NotFiltered(iter.clone()),
+        }
+    }
+}
+
+impl<I, P> Debug for FilterIf<I, P>
+where
+    I: IntoIterator,
+    P: FnMut(&I::Item) -> bool,
+    I::IntoIter: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        use FilterIf::*;
+        match self {
+            Filtered(iter, _) => f.debug_tuple("Filtered").field(iter).finish(),
+            NotFiltered(iter) => f.debug_tuple("NotFiltered").field(iter).finish(),
+        }
+    }
+}
+
+/**
+An iterator adaptor created by [`KaiIterator::skip_if`](trait.KaiIterator.html#method.skip_if)
+*/
+pub enum SkipIf<I>
+where
+    I: IntoIterator,
+{
+    /// Items are skipped. The number is how many items are left to skip.
+    Skipped(I::IntoIter, usize),
+    /// Items are not skipped
+    NotSkipped(I::IntoIter),
+}
+
+impl<I> Iterator for SkipIf<I>
+where
+    I: IntoIterator,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        use SkipIf::*;
+        match self {
+            Skipped(iter, n) if *n > 0 => {
+                let skip = std::mem::take(n);
+                iter.nth(skip)
+            }
+            Skipped(iter, _) | NotSkipped(iter) => iter.next(),
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        use SkipIf::*;
+        match self {
+            Skipped(iter, n) => {
+                let (low, high) = iter.size_hint();
+                (
+                    low.saturating_sub(*n),
+                    high.map(|high| high.saturating_sub(*n)),
+                )
+            }
+            NotSkipped(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl<I> DoubleEndedIterator for SkipIf<I>
+where
+    I: IntoIterator,
+    I::IntoIter: DoubleEndedIterator + ExactSizeIterator,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        use SkipIf::*;
+        match self {
+            // Never yield the items that are still to be skipped
+            Skipped(iter, n) if iter.len() <= *n => None,
+            Skipped(iter, _) | NotSkipped(iter) => iter.next_back(),
+        }
+    }
+}
+
+impl<I> ExactSizeIterator for SkipIf<I>
+where
+    I: IntoIterator,
+    I::IntoIter: ExactSizeIterator,
+{
+}
+
+impl<I> iter::FusedIterator for SkipIf<I>
+where
+    I: IntoIterator,
+    I::IntoIter: iter::FusedIterator,
+{
+}
+
+impl<I> Clone for SkipIf<I>
+where
+    I: IntoIterator,
+    I::IntoIter: Clone,
+{
+    fn clone(&self) -> Self {
+        use SkipIf::*;
+        match self {
+            Skipped(iter, n) => Skipped(iter.clone(), *n),
+            NotSkipped(iter) => NotSkipped(iter.clone()),
+        }
+    }
+}
+
+impl<I> Debug for SkipIf<I>
+where
+    I: IntoIterator,
+    I::IntoIter: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        use SkipIf::*;
+        match self {
+            Skipped(iter, n) => f.debug_tuple("Skipped").field(iter).field(n).finish(),
+            NotSkipped(iter) => f.debug_tuple("NotSkipped").field(iter).finish(),
+        }
+    }
+}
+
+/**
+An iterator adaptor created by [`KaiIterator::take_first_if`](trait.KaiIterator.html#method.take_first_if)
+*/
+pub enum TakeFirstIf<I>
+where
+    I: IntoIterator,
+{
+    /// Only some items are taken. The number is how many items are left to take.
+    Taken(I::IntoIter, usize),
+    /// All items are taken
+    NotTaken(I::IntoIter),
+}
+
+impl<I> Iterator for TakeFirstIf<I>
+where
+    I: IntoIterator,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        use TakeFirstIf::*;
+        match self {
+            Taken(_, 0) => None,
+            Taken(iter, n) => {
+                *n -= 1;
+                iter.next()
+            }
+            NotTaken(iter) => iter.next(),
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        use TakeFirstIf::*;
+        match self {
+            Taken(iter, n) => {
+                let (low, high) = iter.size_hint();
+                (
+                    low.min(*n),
+                    Some(high.map_or(*n, |high| high.min(*n))),
+                )
+            }
+            NotTaken(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl<I> DoubleEndedIterator for TakeFirstIf<I>
+where
+    I: IntoIterator,
+    I::IntoIter: DoubleEndedIterator + ExactSizeIterator,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        use TakeFirstIf::*;
+        match self {
+            Taken(_, 0) => None,
+            Taken(iter, n) => {
+                // Drop the items past the first `n` before yielding the last one that is taken
+                let skip = iter.len().saturating_sub(*n);
+                *n -= 1;
+                iter.nth_back(skip)
+            }
+            NotTaken(iter) => iter.next_back(),
+        }
+    }
+}
+
+impl<I> ExactSizeIterator for TakeFirstIf<I>
+where
+    I: IntoIterator,
+    I::IntoIter: ExactSizeIterator,
+{
+}
+
+impl<I> iter::FusedIterator for TakeFirstIf<I>
+where
+    I: IntoIterator,
+    I::IntoIter: iter::FusedIterator,
+{
+}
+
+impl<I> Clone for TakeFirstIf<I>
+where
+    I: IntoIterator,
+    I::IntoIter: Clone,
+{
+    fn clone(&self) -> Self {
+        use TakeFirstIf::*;
+        match self {
+            Taken(iter, n) => Taken(iter.clone(), *n),
+            NotTaken(iter) => NotTaken(iter.clone()),
+        }
+    }
+}
+
+impl<I> Debug for TakeFirstIf<I>
+where
+    I: IntoIterator,
+    I::IntoIter: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        use TakeFirstIf::*;
+        match self {
+            Taken(iter, n) => f.debug_tuple("Taken").field(iter).field(n).finish(),
+            NotTaken(iter) => f.debug_tuple("NotTaken").field(iter).finish(),
+        }
+    }
+}
+
+/**
+An iterator adaptor created by [`KaiIterator::rev_if`](trait.KaiIterator.html#method.rev_if)
+*/
+pub enum RevIf<I>
+where
+    I: IntoIterator,
+    I::IntoIter: DoubleEndedIterator,
+{
+    /// The iterator is reversed
+    Reversed(I::IntoIter),
+    /// The iterator is not reversed
+    NotReversed(I::IntoIter),
+}
+
+impl<I> Iterator for RevIf<I>
+where
+    I: IntoIterator,
+    I::IntoIter: DoubleEndedIterator,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        use RevIf::*;
+        match self {
+            Reversed(iter) => iter.next_back(),
+            NotReversed(iter) => iter.next(),
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        use RevIf::*;
+        match self {
+            Reversed(iter) | NotReversed(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl<I> DoubleEndedIterator for RevIf<I>
+where
+    I: IntoIterator,
+    I::IntoIter: DoubleEndedIterator,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        use RevIf::*;
+        match self {
+            Reversed(iter) => iter.next(),
+            NotReversed(iter) => iter.next_back(),
+        }
+    }
+}
+
+impl<I> ExactSizeIterator for RevIf<I>
+where
+    I: IntoIterator,
+    I::IntoIter: DoubleEndedIterator + ExactSizeIterator,
+{
+}
+
+impl<I> iter::FusedIterator for RevIf<I>
+where
+    I: IntoIterator,
+    I::IntoIter: DoubleEndedIterator + iter::FusedIterator,
+{
+}
+
+impl<I> Clone for RevIf<I>
+where
+    I: IntoIterator,
+    I::IntoIter: DoubleEndedIterator + Clone,
+{
+    fn clone(&self) -> Self {
+        use RevIf::*;
+        match self {
+            Reversed(iter) => Reversed(iter.clone()),
+            NotReversed(iter) => NotReversed(iter.clone()),
+        }
+    }
+}
+
+impl<I> Debug for RevIf<I>
+where
+    I: IntoIterator,
+    I::IntoIter: DoubleEndedIterator + Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        use RevIf::*;
+        match self {
+            Reversed(iter) => f.debug_tuple("Reversed").field(iter).finish(),
+            NotReversed(iter) => f.debug_tuple("NotReversed").field(iter).finish(),
+        }
+    }
+}
+
+/**
+An iterator adaptor created by [`KaiIterator::inspect_if`](trait.KaiIterator.html#method.inspect_if)
+*/
+pub enum InspectIf<I, F>
+where
+    I: IntoIterator,
+    F: FnMut(&I::Item),
+{
+    /// The iterator is inspected
+    Inspected(I::IntoIter, F),
+    /// The iterator is not inspected
+    NotInspected(I::IntoIter),
+}
+
+impl<I, F> Iterator for InspectIf<I, F>
+where
+    I: IntoIterator,
+    F: FnMut(&I::Item),
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        use InspectIf::*;
+        match self {
+            Inspected(iter, f) => {
+                let item = iter.next();
+                if let Some(item) = &item {
+                    f(item);
+                }
+                item
+            }
+            NotInspected(iter) => iter.next(),
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        use InspectIf::*;
+        match self {
+            Inspected(iter, _) | NotInspected(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl<I, F> DoubleEndedIterator for InspectIf<I, F>
+where
+    I: IntoIterator,
+    F: FnMut(&I::Item),
+    I::IntoIter: DoubleEndedIterator,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        use InspectIf::*;
+        match self {
+            Inspected(iter, f) => {
+                let item = iter.next_back();
+                if let Some(item) = &item {
+                    f(item);
+                }
+                item
+            }
+            NotInspected(iter) => iter.next_back(),
+        }
+    }
+}
+
+impl<I, F> ExactSizeIterator for InspectIf<I, F>
+where
+    I: IntoIterator,
+    F: FnMut(&I::Item),
+    I::IntoIter: ExactSizeIterator,
+{
+}
+
+impl<I, F> iter::FusedIterator for InspectIf<I, F>
+where
+    I: IntoIterator,
+    F: FnMut(&I::Item),
+    I::IntoIter: iter::FusedIterator,
+{
+}
+
+impl<I, F> Clone for InspectIf<I, F>
+where
+    I: IntoIterator,
+    F: FnMut(&I::Item) + Clone,
+    I::IntoIter: Clone,
+{
+    fn clone(&self) -> Self {
+        use InspectIf::*;
+        match self {
+            Inspected(iter, f) => Inspected(iter.clone(), f.clone()),
+            NotInspected(iter) => NotInspected(iter.clone()),
+        }
+    }
+}
+
+impl<I, F> Debug for InspectIf<I, F>
+where
+    I: IntoIterator,
+    F: FnMut(&I::Item),
+    I::IntoIter: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        use InspectIf::*;
+        match self {
+            Inspected(iter, _) => f.debug_tuple("Inspected").field(iter).finish(),
+            NotInspected(iter) => f.debug_tuple("NotInspected").field(iter).finish(),
+        }
+    }
+}
@@ -0,0 +1,406 @@
+use super::*;
+
+use std::backtrace::{Backtrace, BacktraceStatus};
+
+/**
+An error that carries context about where and why something failed
+
+A `ContextError` is made by calling [`Context::context`](trait.Context.html#tymethod.context)
+or [`Context::with_context`](trait.Context.html#tymethod.with_context) on a `Result` or `Option`.
+The original error is kept as the `Error::source`, so contexts can be stacked into a chain of causes.
+
+The `Display` implementation prints the full chain, separated by `: `.
+
+A backtrace is captured when the error is created if the `RUST_BACKTRACE` or
+`RUST_LIB_BACKTRACE` environment variables enable it. It is printed by the `Debug` implementation.
+
+The type parameter is the type of the source error. By default it is
+`dyn Error + Send + Sync`, so the `ContextError` can be sent between threads and
+converts to and from `Box<dyn Error + Send + Sync>` to pair with [`SyncDynResult`](type.SyncDynResult.html).
+Adding context to a [`DynResult`](type.DynResult.html) makes a `ContextError<dyn Error>`,
+which converts to and from `Box<dyn Error>` instead.
+
+# Example
+```
+use kai::*;
+
+fn parse_port(s: &str) -> ContextResult<u16> {
+    s.parse::<u16>().with_context(|| format!("parsing port {:?}", s))
+}
+
+fn load_config() -> ContextResult<u16> {
+    parse_port("http").context("loading config")
+}
+
+let error = load_config().unwrap_err();
+
+assert_eq!(
+    "loading config: parsing port \"http\": invalid digit found in string",
+    error.to_string(),
+);
+assert_eq!(3, error.chain().count());
+
+// Converting to and from `Box<dyn Error + Send + Sync>` keeps the chain
+let boxed: Box<dyn Error + Send + Sync> = error.into();
+let error = ContextError::from(boxed);
+assert_eq!(Some("loading config"), error.context());
+assert_eq!("invalid digit found in string", error.root_cause().to_string());
+
+// Contexts can be added in functions returning a `SyncDynResult` and on other threads
+fn load_port() -> SyncDynResult<u16> {
+    Ok(load_config().context("starting server")?)
+}
+
+let handle = thread::spawn_smart(load_port);
+assert_eq!(
+    "starting server: loading config: parsing port \"http\": invalid digit found in string",
+    handle.join().unwrap().unwrap_err().to_string(),
+);
+
+// Contexts can also be added to a `DynResult`, whose error may not be thread-safe
+fn read_config() -> DynResult<String> {
+    Err(Box::new(std::fmt::Error) as Box<dyn Error>)
+}
+
+fn start() -> DynResult<()> {
+    read_config().context("reading config").context("starting")?;
+    Ok(())
+}
+
+let boxed = start().unwrap_err();
+assert_eq!(
+    "starting: reading config: an error occurred when formatting an argument",
+    boxed.to_string(),
+);
+let error = ContextError::<dyn Error>::from(boxed);
+assert_eq!(Some("starting"), error.context());
+```
+*/
+pub struct ContextError<S: ?Sized = dyn Error + Send + Sync> {
+    context: Option<String>,
+    source: Option<Box<S>>,
+    backtrace: Backtrace,
+}
+
+mod source {
+    use super::*;
+
+    /// The error types that a `ContextError` can use as its source
+    pub trait Source: Error + 'static {
+        fn as_error(&self) -> &(dyn Error + 'static);
+        fn downcast_context(self: Box<Self>) -> Result<Box<ContextError<Self>>, Box<Self>>;
+        fn upcast_context(error: Box<ContextError<Self>>) -> Box<Self>;
+    }
+
+    impl Source for dyn Error {
+        fn as_error(&self) -> &(dyn Error + 'static) {
+            self
+        }
+        fn downcast_context(self: Box<Self>) -> Result<Box<ContextError<Self>>, Box<Self>> {
+            self.downcast()
+        }
+        fn upcast_context(error: Box<ContextError<Self>>) -> Box<Self> {
+            error
+        }
+    }
+
+    impl Source for dyn Error + Send + Sync {
+        fn as_error(&self) -> &(dyn Error + 'static) {
+            self
+        }
+        fn downcast_context(self: Box<Self>) -> Result<Box<ContextError<Self>>, Box<Self>> {
+            self.downcast()
+        }
+        fn upcast_context(error: Box<ContextError<Self>>) -> Box<Self> {
+            error
+        }
+    }
+}
+
+use source::Source;
+
+impl ContextError {
+    /// Create a new `ContextError` with some context and no source
+    pub fn new<C>(context: C) -> Self
+    where
+        C: Display,
+    {
+        ContextError {
+            context: Some(context.to_string()),
+            source: None,
+            backtrace: Backtrace::capture(),
+        }
+    }
+}
+
+impl<S> ContextError<S>
+where
+    S: Source + ?Sized,
+{
+    fn wrap<C>(context: C, source: Box<S>) -> Self
+    where
+        C: Display,
+    {
+        // Reuse the innermost backtrace, which is closest to the actual failure
+        let (source, backtrace) = match source.downcast_context() {
+            Ok(mut inner) => {
+                let backtrace = std::mem::replace(&mut inner.backtrace, Backtrace::disabled());
+                (S::upcast_context(inner), backtrace)
+            }
+            Err(source) => (source, Backtrace::capture()),
+        };
+        ContextError {
+            context: Some(context.to_string()),
+            source: Some(source),
+            backtrace,
+        }
+    }
+    /// Get the context, or `None` if this error was made directly from a boxed error
+    pub fn context(&self) -> Option<&str> {
+        self.context.as_deref()
+    }
+    /**
+    Get the captured backtrace
+
+    Returns `None` if backtraces were not enabled when the error was created.
+    */
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        if self.backtrace.status() == BacktraceStatus::Captured {
+            Some(&self.backtrace)
+        } else {
+            None
+        }
+    }
+    /// Iterate over this error and each of its causes
+    pub fn chain(&self) -> impl Iterator<Item = &(dyn Error + 'static)> {
+        iter::successors(Some(self as &(dyn Error + 'static)), |&error| error.source())
+    }
+    /// Get the innermost cause of this error
+    pub fn root_cause(&self) -> &(dyn Error + 'static) {
+        self.chain().last().unwrap()
+    }
+    fn parts(&self) -> ContextParts<'_> {
+        ContextParts {
+            context: self.context(),
+            source: self.source.as_deref().map(S::as_error),
+            backtrace: self.backtrace(),
+        }
+    }
+}
+
+/// The fields of a `ContextError` of either source type
+pub(crate) struct ContextParts<'a> {
+    pub context: Option<&'a str>,
+    pub source: Option<&'a (dyn Error + 'static)>,
+    pub backtrace: Option<&'a Backtrace>,
+}
+
+impl<'a> ContextParts<'a> {
+    /// Get the parts of an error if it is a `ContextError`
+    pub fn of(error: &'a (dyn Error + 'static)) -> Option<Self> {
+        match error.downcast_ref::<ContextError>() {
+            Some(error) => Some(error.parts()),
+            None => error
+                .downcast_ref::<ContextError<dyn Error>>()
+                .map(ContextError::parts),
+        }
+    }
+    /// Get the error that this error is transparent over, if it has no context
+    pub fn transparent(&self) -> Option<&'a (dyn Error + 'static)> {
+        match self.context {
+            None => self.source,
+            Some(_) => None,
+        }
+    }
+}
+
+/// Display a single link of a chain without its causes
+pub(crate) fn fmt_link(error: &(dyn Error + 'static), f: &mut Formatter) -> FmtResult {
+    match ContextParts::of(error) {
+        Some(ContextParts {
+            context: Some(context),
+            ..
+        }) => write!(f, "{}", context),
+        Some(ContextParts {
+            source: Some(source),
+            ..
+        }) => write!(f, "{}", source),
+        _ => write!(f, "{}", error),
+    }
+}
+
+/// Display an error and each of its causes, separated by `: `
+pub(crate) fn fmt_chain(error: &(dyn Error + 'static), f: &mut Formatter) -> FmtResult {
+    let chain = iter::successors(Some(error), |&error| error.source());
+    for (i, error) in chain.enumerate() {
+        if i > 0 {
+            write!(f, ": ")?;
+        }
+        fmt_link(error, f)?;
+    }
+    Ok(())
+}
+
+impl<S> Display for ContextError<S>
+where
+    S: Source + ?Sized,
+{
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        fmt_chain(self, f)
+    }
+}
+
+impl<S> Debug for ContextError<S>
+where
+    S: Source + ?Sized,
+{
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        Display::fmt(self, f)?;
+        if let Some(backtrace) = self.backtrace() {
+            write!(f, "\n\nStack backtrace:\n{}", backtrace)?;
+        }
+        Ok(())
+    }
+}
+
+impl<S> Error for ContextError<S>
+where
+    S: Source + ?Sized,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        let source = self.source.as_deref().map(S::as_error);
+        match (&self.context, source) {
+            (Some(_), source) => source,
+            // Without context, this error is transparent over its source
+            (None, source) => source.and_then(Error::source),
+        }
+    }
+}
+
+impl<S> From<Box<S>> for ContextError<S>
+where
+    S: Source + ?Sized,
+{
+    fn from(error: Box<S>) -> Self {
+        match error.downcast_context() {
+            Ok(error) => *error,
+            Err(error) => ContextError {
+                context: None,
+                source: Some(error),
+                backtrace: Backtrace::capture(),
+            },
+        }
+    }
+}
+
+impl From<ContextError> for ContextError<dyn Error> {
+    fn from(error: ContextError) -> Self {
+        ContextError {
+            context: error.context,
+            source: error.source.map(|source| source as Box<dyn Error>),
+            backtrace: error.backtrace,
+        }
+    }
+}
+
+/**
+Adds context to errors and `None`s
+
+This trait is implemented for `Result`s whose errors can be converted into
+`Box<dyn Error + Send + Sync>`, for `Option`s, which both make a [`ContextError`](struct.ContextError.html),
+and for [`DynResult`](type.DynResult.html)s, which make a `ContextError<dyn Error>`.
+
+The type parameter is the source type of the `ContextError` that is made.
+It never has to be written out, because each type only implements one of them.
+The error type of the `Result` must be known, though, so a call like
+`s.parse().context(..)` needs the parsed type written out, as in `s.parse::<i32>()`.
+
+# Example
+```
+use kai::*;
+
+let config: Option<&str> = None;
+let error = config.context("no config found").unwrap_err();
+
+assert_eq!("no config found", error.to_string());
+assert!(error.source().is_none());
+```
+*/
+pub trait Context<T, S: ?Sized = dyn Error + Send + Sync> {
+    /// Wrap the error, or the `None`, in a [`ContextError`](struct.ContextError.html) with the given context
+    fn context<C>(self, context: C) -> Result<T, ContextError<S>>
+    where
+        C: Display;
+    /// Like [`Context::context`](trait.Context.html#tymethod.context), but the context is only created if there is an error
+    fn with_context<C, F>(self, f: F) -> Result<T, ContextError<S>>
+    where
+        C: Display,
+        F: FnOnce() -> C;
+}
+
+impl<T, E> Context<T> for Result<T, E>
+where
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    fn context<C>(self, context: C) -> Result<T, ContextError>
+    where
+        C: Display,
+    {
+        self.map_err(|e| ContextError::wrap(context, e.into()))
+    }
+    fn with_context<C, F>(self, f: F) -> Result<T, ContextError>
+    where
+        C: Display,
+        F: FnOnce() -> C,
+    {
+        self.map_err(|e| ContextError::wrap(f(), e.into()))
+    }
+}
+
+impl<T> Context<T, dyn Error> for DynResult<T> {
+    fn context<C>(self, context: C) -> Result<T, ContextError<dyn Error>>
+    where
+        C: Display,
+    {
+        self.map_err(|e| ContextError::wrap(context, e))
+    }
+    fn with_context<C, F>(self, f: F) -> Result<T, ContextError<dyn Error>>
+    where
+        C: Display,
+        F: FnOnce() -> C,
+    {
+        self.map_err(|e| ContextError::wrap(f(), e))
+    }
+}
+
+impl<T> Context<T, dyn Error> for Result<T, ContextError<dyn Error>> {
+    fn context<C>(self, context: C) -> Result<T, ContextError<dyn Error>>
+    where
+        C: Display,
+    {
+        self.map_err(|e| ContextError::wrap(context, Box::new(e) as Box<dyn Error>))
+    }
+    fn with_context<C, F>(self, f: F) -> Result<T, ContextError<dyn Error>>
+    where
+        C: Display,
+        F: FnOnce() -> C,
+    {
+        self.map_err(|e| ContextError::wrap(f(), Box::new(e) as Box<dyn Error>))
+    }
+}
+
+impl<T> Context<T> for Option<T> {
+    fn context<C>(self, context: C) -> Result<T, ContextError>
+    where
+        C: Display,
+    {
+        self.ok_or_else(|| ContextError::new(context))
+    }
+    fn with_context<C, F>(self, f: F) -> Result<T, ContextError>
+    where
+        C: Display,
+        F: FnOnce() -> C,
+    {
+        self.ok_or_else(|| ContextError::new(f()))
+    }
+}
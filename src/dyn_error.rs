@@ -0,0 +1,256 @@
+use super::*;
+
+/**
+Converts between [`DynResult`](type.DynResult.html) and [`SyncDynResult`](type.SyncDynResult.html)
+
+# Example
+```
+use kai::*;
+
+let sync: SyncDynResult<()> = Err("failed".into());
+let dynamic: DynResult<()> = sync.into_dyn();
+assert_eq!("failed", dynamic.unwrap_err().to_string());
+
+let dynamic: DynResult<()> = Err(ContextError::new("failed").into());
+let sync: SyncDynResult<()> = dynamic.into_sync();
+let handle = thread::spawn(move || sync);
+let error = handle.join().unwrap().unwrap_err();
+assert_eq!(Some("failed"), error.downcast_ref::<ContextError>().unwrap().context());
+
+let dynamic: DynResult<()> = Err(Box::new(std::fmt::Error));
+let sync: SyncDynResult<()> = dynamic.into_sync();
+assert_eq!("an error occurred when formatting an argument", sync.unwrap_err().to_string());
+```
+*/
+pub trait DynConvert<T> {
+    /// Convert into a [`DynResult`](type.DynResult.html)
+    fn into_dyn(self) -> DynResult<T>;
+    /**
+    Convert into a [`SyncDynResult`](type.SyncDynResult.html)
+
+    A [`ContextError`](struct.ContextError.html) is moved across as it is.
+    Because any other `Box<dyn Error>` may not be thread-safe, converting one replaces
+    it with an error whose message is the full cause chain.
+    */
+    fn into_sync(self) -> SyncDynResult<T>;
+}
+
+impl<T> DynConvert<T> for DynResult<T> {
+    fn into_dyn(self) -> DynResult<T> {
+        self
+    }
+    fn into_sync(self) -> SyncDynResult<T> {
+        self.map_err(|e| match e.downcast::<ContextError>() {
+            Ok(e) => e as Box<dyn Error + Send + Sync>,
+            Err(e) => ChainDisplay(e.as_ref()).to_string().into(),
+        })
+    }
+}
+
+impl<T> DynConvert<T> for SyncDynResult<T> {
+    fn into_dyn(self) -> DynResult<T> {
+        self.map_err(|e| e as Box<dyn Error>)
+    }
+    fn into_sync(self) -> SyncDynResult<T> {
+        self
+    }
+}
+
+struct ChainDisplay<'a>(&'a (dyn Error + 'static));
+
+impl<'a> Display for ChainDisplay<'a> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        context::fmt_chain(self.0, f)
+    }
+}
+
+/**
+Searches an error and each of its causes for an error of a given type
+
+Unlike `is` and `downcast_ref` on `dyn Error`, these search the whole chain of
+`Error::source`s, so they still find an error that has been wrapped with
+[`Context::context`](trait.Context.html#tymethod.context).
+They are also implemented for `Result`s, in which case they check the error, if there is one.
+
+The methods have different names than those of `dyn Error` so that they do not
+shadow them on boxed errors.
+
+# Example
+```
+use kai::*;
+
+#[derive(Debug)]
+struct NotFound;
+
+impl Display for NotFound {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "not found")
+    }
+}
+
+impl Error for NotFound {}
+
+let result: Result<(), _> = Err(NotFound).context("loading user");
+
+assert!(result.has_cause::<NotFound>());
+assert!(!result.has_cause::<std::io::Error>());
+
+let error: Box<dyn Error> = result.unwrap_err().into();
+assert!(error.has_cause::<NotFound>());
+assert!(error.find_cause::<NotFound>().is_some());
+
+// The methods of `dyn Error` only check the outermost error
+assert!(!error.is::<NotFound>());
+assert!(error.is::<ContextError>());
+```
+*/
+pub trait Downcast {
+    /// Check if the error or any of its causes is an `E`
+    fn has_cause<E>(&self) -> bool
+    where
+        E: Error + 'static,
+    {
+        self.find_cause::<E>().is_some()
+    }
+    /// Get a reference to the first error in the chain that is an `E`
+    fn find_cause<E>(&self) -> Option<&E>
+    where
+        E: Error + 'static;
+}
+
+fn search_chain<'a, E>(error: &'a (dyn Error + 'static)) -> Option<&'a E>
+where
+    E: Error + 'static,
+{
+    iter::successors(Some(error), |&error| error.source()).find_map(|error| {
+        error.downcast_ref::<E>().or_else(|| {
+            context::ContextParts::of(error)
+                .and_then(|parts| parts.transparent())
+                .and_then(|error| error.downcast_ref::<E>())
+        })
+    })
+}
+
+impl Downcast for Box<dyn Error> {
+    fn find_cause<E>(&self) -> Option<&E>
+    where
+        E: Error + 'static,
+    {
+        search_chain(self.as_ref())
+    }
+}
+
+impl Downcast for Box<dyn Error + Send + Sync> {
+    fn find_cause<E>(&self) -> Option<&E>
+    where
+        E: Error + 'static,
+    {
+        search_chain(self.as_ref())
+    }
+}
+
+impl Downcast for ContextError {
+    fn find_cause<E>(&self) -> Option<&E>
+    where
+        E: Error + 'static,
+    {
+        search_chain(self)
+    }
+}
+
+impl Downcast for ContextError<dyn Error> {
+    fn find_cause<E>(&self) -> Option<&E>
+    where
+        E: Error + 'static,
+    {
+        search_chain(self)
+    }
+}
+
+impl<T, D> Downcast for Result<T, D>
+where
+    D: Downcast,
+{
+    fn find_cause<E>(&self) -> Option<&E>
+    where
+        E: Error + 'static,
+    {
+        self.as_ref().err().and_then(D::find_cause)
+    }
+}
+
+/**
+Returns early with an error
+
+The error can be a format string with arguments or any expression that converts
+into the function's error type. Works in functions returning [`DynResult`](type.DynResult.html),
+[`SyncDynResult`](type.SyncDynResult.html), or any `Result` whose error implements `From<String>`.
+
+# Example
+```
+use kai::*;
+
+fn check_name(name: &str) -> SyncDynResult<()> {
+    if name.is_empty() {
+        bail!("name is empty");
+    }
+    if name.len() > 8 {
+        bail!("name {:?} is longer than {} characters", name, 8);
+    }
+    Ok(())
+}
+
+assert!(check_name("kai").is_ok());
+assert_eq!("name is empty", check_name("").unwrap_err().to_string());
+
+let handle = thread::spawn_smart(|| check_name("abcdefghij"));
+assert_eq!(
+    "name \"abcdefghij\" is longer than 8 characters",
+    handle.join().unwrap().unwrap_err().to_string(),
+);
+```
+*/
+#[macro_export]
+macro_rules! bail {
+    ($fmt:literal $(, $arg:expr)* $(,)?) => {
+        return ::std::result::Result::Err(::std::convert::From::from(format!($fmt $(, $arg)*)))
+    };
+    ($error:expr $(,)?) => {
+        return ::std::result::Result::Err(::std::convert::From::from($error))
+    };
+}
+
+/**
+Returns early with an error if a condition is false
+
+The message arguments are the same as those of [`bail!`](macro.bail.html).
+If there are none, the message says which condition failed.
+
+# Example
+```
+use kai::*;
+
+fn divide(a: i32, b: i32) -> DynResult<i32> {
+    ensure!(b != 0, "cannot divide {} by zero", a);
+    ensure!(a >= 0);
+    Ok(a / b)
+}
+
+assert_eq!(2, divide(4, 2).unwrap());
+assert_eq!("cannot divide 4 by zero", divide(4, 0).unwrap_err().to_string());
+assert_eq!("condition failed: `a >= 0`", divide(-4, 2).unwrap_err().to_string());
+```
+*/
+#[macro_export]
+macro_rules! ensure {
+    ($condition:expr $(,)?) => {
+        if !$condition {
+            $crate::bail!(concat!("condition failed: `", stringify!($condition), "`"));
+        }
+    };
+    ($condition:expr, $($message:tt)+) => {
+        if !$condition {
+            $crate::bail!($($message)+);
+        }
+    };
+}
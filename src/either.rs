@@ -0,0 +1,234 @@
+use super::*;
+
+/**
+A value that is one of two types
+
+`Either` implements many traits, such as `Iterator`, `Read`, `Write`, `Display`, and `Error`,
+as long as both sides implement them. This allows functions to return one of two
+concrete types without boxing.
+
+See also [`KaiIterator::left_if`](trait.KaiIterator.html#method.left_if)
+and [`KaiIterator::right_if`](trait.KaiIterator.html#method.right_if).
+
+# Example
+```
+use kai::*;
+
+fn numbers(evens: bool) -> impl Iterator<Item = u32> {
+    if evens {
+        Either::Left((0..10).step_by(2))
+    } else {
+        Either::Right(vec![1, 3, 5].into_iter())
+    }
+}
+
+assert_eq!(vec![0, 2, 4, 6, 8], numbers(true).collect::<Vec<_>>());
+assert_eq!(vec![1, 3, 5], numbers(false).collect::<Vec<_>>());
+
+fn reader(text: Option<&'static str>) -> impl BufRead {
+    match text {
+        Some(text) => Either::Left(text.as_bytes()),
+        None => Either::Right(std::io::empty()),
+    }
+}
+
+assert_eq!(2, reader(Some("a\nb")).lines().count());
+assert_eq!(0, reader(None).lines().count());
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Either<L, R> {
+    /// The left value
+    Left(L),
+    /// The right value
+    Right(R),
+}
+
+use Either::*;
+
+macro_rules! either {
+    ($value:expr, $inner:ident => $result:expr) => {
+        match $value {
+            Left($inner) => $result,
+            Right($inner) => $result,
+        }
+    };
+}
+
+impl<L, R> Either<L, R> {
+    /// Check if this is a `Left`
+    pub fn is_left(&self) -> bool {
+        matches!(self, Left(_))
+    }
+    /// Check if this is a `Right`
+    pub fn is_right(&self) -> bool {
+        matches!(self, Right(_))
+    }
+    /// Get the left value, if there is one
+    pub fn left(self) -> Option<L> {
+        variant!(Left(l) = self => l)
+    }
+    /// Get the right value, if there is one
+    pub fn right(self) -> Option<R> {
+        variant!(Right(r) = self => r)
+    }
+    /// Convert from `&Either<L, R>` to `Either<&L, &R>`
+    pub fn as_ref(&self) -> Either<&L, &R> {
+        match self {
+            Left(l) => Left(l),
+            Right(r) => Right(r),
+        }
+    }
+    /// Convert from `&mut Either<L, R>` to `Either<&mut L, &mut R>`
+    pub fn as_mut(&mut self) -> Either<&mut L, &mut R> {
+        match self {
+            Left(l) => Left(l),
+            Right(r) => Right(r),
+        }
+    }
+    /// Swap the left and right sides
+    pub fn flip(self) -> Either<R, L> {
+        match self {
+            Left(l) => Right(l),
+            Right(r) => Left(r),
+        }
+    }
+    /// Transform the left value
+    pub fn map_left<F, M>(self, f: F) -> Either<M, R>
+    where
+        F: FnOnce(L) -> M,
+    {
+        match self {
+            Left(l) => Left(f(l)),
+            Right(r) => Right(r),
+        }
+    }
+    /// Transform the right value
+    pub fn map_right<F, M>(self, f: F) -> Either<L, M>
+    where
+        F: FnOnce(R) -> M,
+    {
+        match self {
+            Left(l) => Left(l),
+            Right(r) => Right(f(r)),
+        }
+    }
+    /// Transform whichever value there is into a single type
+    pub fn either<F, G, T>(self, f: F, g: G) -> T
+    where
+        F: FnOnce(L) -> T,
+        G: FnOnce(R) -> T,
+    {
+        match self {
+            Left(l) => f(l),
+            Right(r) => g(r),
+        }
+    }
+}
+
+impl<T> Either<T, T> {
+    /// Take whichever value there is when both sides are the same type
+    pub fn into_inner(self) -> T {
+        either!(self, inner => inner)
+    }
+}
+
+impl<L, R> Iterator for Either<L, R>
+where
+    L: Iterator,
+    R: Iterator<Item = L::Item>,
+{
+    type Item = L::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        either!(self, iter => iter.next())
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        either!(self, iter => iter.size_hint())
+    }
+    fn fold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        either!(self, iter => iter.fold(init, f))
+    }
+}
+
+impl<L, R> DoubleEndedIterator for Either<L, R>
+where
+    L: DoubleEndedIterator,
+    R: DoubleEndedIterator<Item = L::Item>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        either!(self, iter => iter.next_back())
+    }
+}
+
+impl<L, R> ExactSizeIterator for Either<L, R>
+where
+    L: ExactSizeIterator,
+    R: ExactSizeIterator<Item = L::Item>,
+{
+}
+
+impl<L, R> iter::FusedIterator for Either<L, R>
+where
+    L: iter::FusedIterator,
+    R: iter::FusedIterator<Item = L::Item>,
+{
+}
+
+impl<L, R> Read for Either<L, R>
+where
+    L: Read,
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        either!(self, reader => reader.read(buf))
+    }
+}
+
+impl<L, R> BufRead for Either<L, R>
+where
+    L: BufRead,
+    R: BufRead,
+{
+    fn fill_buf(&mut self) -> IoResult<&[u8]> {
+        either!(self, reader => reader.fill_buf())
+    }
+    fn consume(&mut self, amt: usize) {
+        either!(self, reader => reader.consume(amt))
+    }
+}
+
+impl<L, R> Write for Either<L, R>
+where
+    L: Write,
+    R: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        either!(self, writer => writer.write(buf))
+    }
+    fn flush(&mut self) -> IoResult<()> {
+        either!(self, writer => writer.flush())
+    }
+}
+
+impl<L, R> Display for Either<L, R>
+where
+    L: Display,
+    R: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        either!(self, inner => inner.fmt(f))
+    }
+}
+
+impl<L, R> Error for Either<L, R>
+where
+    L: Error,
+    R: Error,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        either!(self, error => error.source())
+    }
+}
@@ -0,0 +1,127 @@
+use super::*;
+
+/**
+A non-empty collection of errors
+
+`Errors` is returned by [`KaiIterator::collect_all_errors`](trait.KaiIterator.html#method.collect_all_errors)
+and [`KaiIterator::try_for_each_all`](trait.KaiIterator.html#method.try_for_each_all).
+It implements `Error` as long as the errors do, so it can be returned from
+functions that return a [`DynResult`](type.DynResult.html) with `?`.
+
+# Example
+```
+use kai::*;
+
+fn validate(inputs: &[&str]) -> DynResult<Vec<u8>> {
+    Ok(inputs.iter().map(|s| s.parse::<u8>()).collect_all_errors()?)
+}
+
+assert_eq!(vec![1, 2], validate(&["1", "2"]).unwrap());
+
+let error = validate(&["1", "x", "300"]).unwrap_err();
+assert_eq!(
+    "2 errors occurred:\n  invalid digit found in string\n  number too large to fit in target type",
+    error.to_string(),
+);
+```
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Errors<E>(Vec<E>);
+
+impl<E> Errors<E> {
+    pub(crate) fn new(errors: Vec<E>) -> Self {
+        debug_assert!(!errors.is_empty());
+        Errors(errors)
+    }
+    /// Get the errors as a `Vec`
+    pub fn into_vec(self) -> Vec<E> {
+        self.0
+    }
+}
+
+impl<E> Deref for Errors<E> {
+    type Target = [E];
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E> IntoIterator for Errors<E> {
+    type Item = E;
+    type IntoIter = std::vec::IntoIter<E>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, E> IntoIterator for &'a Errors<E> {
+    type Item = &'a E;
+    type IntoIter = std::slice::Iter<'a, E>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<E> From<Errors<E>> for Vec<E> {
+    fn from(errors: Errors<E>) -> Self {
+        errors.0
+    }
+}
+
+impl<E> Display for Errors<E>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        if let [error] = self.0.as_slice() {
+            return error.fmt(f);
+        }
+        write!(f, "{} errors occurred:", self.0.len())?;
+        for error in &self.0 {
+            write!(f, "\n  {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl<E> Error for Errors<E> where E: Debug + Display {}
+
+/**
+The error returned by [`variant_or!`](macro.variant_or.html) when the pattern does not match
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantError {
+    expected: &'static str,
+    found: String,
+}
+
+impl VariantError {
+    /// Create a new `VariantError` from the expected pattern and the unmatched value
+    pub fn new<T>(expected: &'static str, found: &T) -> Self
+    where
+        T: Debug,
+    {
+        let found = format!("{:?}", found);
+        let end = found.find(['(', ' ', '{']).unwrap_or(found.len());
+        VariantError {
+            expected,
+            found: found[..end].to_string(),
+        }
+    }
+    /// Get the pattern that was expected
+    pub fn expected(&self) -> &str {
+        self.expected
+    }
+    /// Get the name of the variant that was found
+    pub fn found(&self) -> &str {
+        &self.found
+    }
+}
+
+impl Display for VariantError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "expected {}, found {}", self.expected, self.found)
+    }
+}
+
+impl Error for VariantError {}
@@ -0,0 +1,326 @@
+use super::*;
+
+use std::{collections::VecDeque, iter::Peekable};
+
+/**
+An iterator adaptor created by [`KaiIterator::group_by_key`](trait.KaiIterator.html#method.group_by_key)
+*/
+pub struct GroupByKey<I, F, K>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    iter: I,
+    f: F,
+    pending: Option<(K, I::Item)>,
+}
+
+impl<I, F, K> GroupByKey<I, F, K>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    pub(crate) fn new(iter: I, f: F) -> Self {
+        GroupByKey {
+            iter,
+            f,
+            pending: None,
+        }
+    }
+}
+
+impl<I, F, K> Iterator for GroupByKey<I, F, K>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    type Item = (K, Vec<I::Item>);
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, first) = match self.pending.take() {
+            Some(pending) => pending,
+            None => {
+                let item = self.iter.next()?;
+                ((self.f)(&item), item)
+            }
+        };
+        let mut group = vec![first];
+        for item in &mut self.iter {
+            let next_key = (self.f)(&item);
+            if next_key == key {
+                group.push(item);
+            } else {
+                self.pending = Some((next_key, item));
+                break;
+            }
+        }
+        Some((key, group))
+    }
+}
+
+/**
+An iterator adaptor created by [`KaiIterator::into_chunk_by`](trait.KaiIterator.html#method.into_chunk_by)
+*/
+pub struct IntoChunkBy<I, P>
+where
+    I: Iterator,
+    P: FnMut(&I::Item, &I::Item) -> bool,
+{
+    iter: I,
+    predicate: P,
+    pending: Option<I::Item>,
+}
+
+impl<I, P> IntoChunkBy<I, P>
+where
+    I: Iterator,
+    P: FnMut(&I::Item, &I::Item) -> bool,
+{
+    pub(crate) fn new(iter: I, predicate: P) -> Self {
+        IntoChunkBy {
+            iter,
+            predicate,
+            pending: None,
+        }
+    }
+}
+
+impl<I, P> Iterator for IntoChunkBy<I, P>
+where
+    I: Iterator,
+    P: FnMut(&I::Item, &I::Item) -> bool,
+{
+    type Item = Vec<I::Item>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.pending.take().or_else(|| self.iter.next())?;
+        let mut chunk = vec![first];
+        for item in &mut self.iter {
+            if (self.predicate)(chunk.last().unwrap(), &item) {
+                chunk.push(item);
+            } else {
+                self.pending = Some(item);
+                break;
+            }
+        }
+        Some(chunk)
+    }
+}
+
+/**
+An iterator adaptor created by [`KaiIterator::into_chunks`](trait.KaiIterator.html#method.into_chunks)
+*/
+#[derive(Debug, Clone)]
+pub struct IntoChunks<I> {
+    iter: I,
+    size: usize,
+}
+
+impl<I> IntoChunks<I> {
+    pub(crate) fn new(iter: I, size: usize) -> Self {
+        assert!(size != 0, "chunk size must be non-zero");
+        IntoChunks { iter, size }
+    }
+}
+
+impl<I> Iterator for IntoChunks<I>
+where
+    I: Iterator,
+{
+    type Item = Vec<I::Item>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk: Vec<_> = (&mut self.iter).take(self.size).collect();
+        if chunk.is_empty() {
+            None
+        } else {
+            Some(chunk)
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.iter.size_hint();
+        (
+            low.div_ceil(self.size),
+            high.map(|high| high.div_ceil(self.size)),
+        )
+    }
+}
+
+/**
+An iterator adaptor created by [`KaiIterator::into_windows`](trait.KaiIterator.html#method.into_windows)
+*/
+#[derive(Debug, Clone)]
+pub struct IntoWindows<I>
+where
+    I: Iterator,
+{
+    iter: I,
+    size: usize,
+    window: VecDeque<I::Item>,
+}
+
+impl<I> IntoWindows<I>
+where
+    I: Iterator,
+{
+    pub(crate) fn new(iter: I, size: usize) -> Self {
+        assert!(size != 0, "window size must be non-zero");
+        IntoWindows {
+            iter,
+            size,
+            window: VecDeque::with_capacity(size),
+        }
+    }
+}
+
+impl<I> Iterator for IntoWindows<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.window.len() == self.size {
+            self.window.pop_front();
+        }
+        while self.window.len() < self.size {
+            self.window.push_back(self.iter.next()?);
+        }
+        Some(self.window.iter().cloned().collect())
+    }
+}
+
+/**
+An iterator adaptor created by [`KaiIterator::interleave`](trait.KaiIterator.html#method.interleave)
+*/
+#[derive(Debug, Clone)]
+pub struct Interleave<I, J> {
+    first: I,
+    second: J,
+    use_second: bool,
+}
+
+impl<I, J> Interleave<I, J> {
+    pub(crate) fn new(first: I, second: J) -> Self {
+        Interleave {
+            first,
+            second,
+            use_second: false,
+        }
+    }
+}
+
+impl<I, J> Iterator for Interleave<I, J>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.use_second = !self.use_second;
+        if self.use_second {
+            self.first.next().or_else(|| self.second.next())
+        } else {
+            self.second.next().or_else(|| self.first.next())
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (first_low, first_high) = self.first.size_hint();
+        let (second_low, second_high) = self.second.size_hint();
+        let high = match (first_high, second_high) {
+            (Some(first), Some(second)) => first.checked_add(second),
+            _ => None,
+        };
+        (first_low.saturating_add(second_low), high)
+    }
+}
+
+/**
+An iterator adaptor created by [`KaiIterator::into_intersperse`](trait.KaiIterator.html#method.into_intersperse)
+*/
+pub struct IntoIntersperse<I>
+where
+    I: Iterator,
+{
+    iter: Peekable<I>,
+    separator: I::Item,
+    needs_separator: bool,
+}
+
+impl<I> IntoIntersperse<I>
+where
+    I: Iterator,
+{
+    pub(crate) fn new(iter: I, separator: I::Item) -> Self {
+        IntoIntersperse {
+            iter: iter.peekable(),
+            separator,
+            needs_separator: false,
+        }
+    }
+}
+
+impl<I> Iterator for IntoIntersperse<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.needs_separator && self.iter.peek().is_some() {
+            self.needs_separator = false;
+            Some(self.separator.clone())
+        } else {
+            self.needs_separator = true;
+            self.iter.next()
+        }
+    }
+}
+
+/**
+An iterator adaptor created by [`KaiIterator::into_dedup_by_key`](trait.KaiIterator.html#method.into_dedup_by_key)
+*/
+pub struct IntoDedupByKey<I, F, K>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    iter: I,
+    f: F,
+    last: Option<K>,
+}
+
+impl<I, F, K> IntoDedupByKey<I, F, K>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    pub(crate) fn new(iter: I, f: F) -> Self {
+        IntoDedupByKey { iter, f, last: None }
+    }
+}
+
+impl<I, F, K> Iterator for IntoDedupByKey<I, F, K>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        for item in &mut self.iter {
+            let key = (self.f)(&item);
+            if self.last.as_ref() != Some(&key) {
+                self.last = Some(key);
+                return Some(item);
+            }
+        }
+        None
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.iter.size_hint();
+        ((low > 0) as usize, high)
+    }
+}
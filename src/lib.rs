@@ -13,6 +13,12 @@ This library contains my personal Rust prelude and utilities.
 
 This library is meant to improve your experience writing Rust no matter what you are writing code for. The patterns it tackles are mostly ones that the average Rust programmer encounters on a daily basis.
 
+# Cargo features
+
+* `derive` Enables [`#[derive(Close)]`](https://docs.rs/kai-derive) for comparing structs with floating-point fields,
+  and [`#[derive(Variants)]`](https://docs.rs/kai-derive) for generating enum variant accessors
+* `serde` Implements `Serialize` and `Deserialize` for [`Swap`](struct.Swap.html) and [`ThreadStatus`](thread/enum.ThreadStatus.html), and `Serialize` for [`Adapter`](struct.Adapter.html)
+
 # Utilities
 
 I have made some very simple utilities to aid in writing Rust code:
@@ -23,26 +29,55 @@ I have made some very simple utilities to aid in writing Rust code:
 ### Functions
 * [`order`](order/index.html) Functions for fully ordering `PartialOrd` types
 * [`close`](close/index.html) Functions for checking if two floating-point numbers are close enough to be considered equal
+* [`into_bool`](fn.into_bool.html) Deprecated conversion for calling [`BoolMap`](trait.BoolMap.html) methods on `Into<bool>` types
 * [`promote_then`](fn.promote_then.html) Temporarily gain access to an immutable reference as mutable
+* [`run`](fn.run.html) Runs a program's main logic, printing any error and its causes to stderr
 
 ### Traits
-* [`BoolMap`](trait.BoolMap.html) Maps `bool`s to `Option`s in one line
+* [`BoolMap`](trait.BoolMap.html) Maps `bool`s to `Option`s and `Result`s in one line
 * [`Bind`](trait.Bind.html) Allows the binding and mutation of a value in a single line
-* [`KaiIterator`](trait.KaiIterator.html) Generates my custom iterator adapters
+* [`Context`](trait.Context.html) Adds context to errors and `None`s
+* [`Downcast`](trait.Downcast.html) Searches an error and each of its causes for an error of a given type
+* [`DynConvert`](trait.DynConvert.html) Converts between `DynResult` and `SyncDynResult`
+* [`Report`](trait.Report.html) Chooses how an error is reported by `run`, including its exit code
+* [`Close`](trait.Close.html) Checks if two values made of floating-point numbers are close enough to be considered equal
+* [`KaiIterator`](trait.KaiIterator.html) Generates my custom iterator adapters, grouping adapters, and float statistics
 
 ### Structs
 * [`Adapter`](struct.Adapter.html) Wraps a reference to a string representation of some type
+* [`ContextError`](struct.ContextError.html) An error that carries context about where and why something failed
+* [`VariantError`](struct.VariantError.html) The error returned by `variant_or!` when the pattern does not match
+* [`Errors`](struct.Errors.html) A non-empty collection of errors, such as from [`KaiIterator::collect_all_errors`](trait.KaiIterator.html#method.collect_all_errors)
+* [`NotNan`](struct.NotNan.html) A float that is guaranteed not to be NaN
+* [`OrdF32`](struct.OrdF32.html) and [`OrdF64`](struct.OrdF64.html) Totally ordered floats
+* [`ObservedSwap`](struct.ObservedSwap.html) Wrapper that allows consuming transformations and notifies observers of changes
 * [`Swap`](struct.Swap.html) Wrapper that allows consuming transformations on borrowed data
+* [`SyncSwap`](struct.SyncSwap.html) Thread-safe wrapper that allows consuming transformations on shared data
+* [`UndoSwap`](struct.UndoSwap.html) Wrapper that allows consuming transformations with undo and redo
+
+### Enums
+* [`Either`](enum.Either.html) A value that is one of two types
+* [`UndoEviction`](enum.UndoEviction.html) What an `UndoSwap` does with its history when it reaches its capacity
 
 ### Types
 * [`DynResult`](type.DynResult.html) A dynamic `Result` type
+* [`SyncDynResult`](type.SyncDynResult.html) A dynamic `Result` type whose error can be sent between threads
+* [`ContextResult`](type.ContextResult.html) A `Result` whose error is a [`ContextError`](struct.ContextError.html)
 * [`IoResult`](type.IoResult.html) An alias for `io::Result`
 * [`FmtResult`](type.FmtResult.html) An alias for `fmt::Result`
 
 ### Macros
 * [`variant!`](macro.variant.html) Maps an enum to an option for use with `Iterator::filter_map`
+* [`variant_or!`](macro.variant_or.html) Maps an enum to a `Result` with an error describing the unmatched variant
+* [`variant_ref!`](macro.variant_ref.html) and [`variant_mut!`](macro.variant_mut.html) Map a borrowed enum to an option
 * [`transparent_mod!`](macro.transparent_mod.html) Declares transparent external child modules
 * [`cond_vec!`](macro.cond_vec.html) Conditionally construct `Vec`s
+* [`pipe!`](macro.pipe.html) Pipes a value through a series of functions, reading left to right
+* [`bail!`](macro.bail.html) Returns early with an error
+* [`ensure!`](macro.ensure.html) Returns early with an error if a condition is false
+* [`assert_close!`](macro.assert_close.html) Asserts that two values are close enough to be considered equal
+* [`assert_close_rel!`](macro.assert_close_rel.html) Asserts closeness using a relative tolerance
+* [`debug_assert_close!`](macro.debug_assert_close.html) Asserts closeness only in debug builds
 */
 
 /**
@@ -77,9 +112,29 @@ macro_rules! transparent_mod {
     }
 }
 
-transparent_mod!(adapter, swap);
+transparent_mod!(
+    adapter,
+    cond_iter,
+    context,
+    dyn_error,
+    either,
+    errors,
+    group_iter,
+    observed_swap,
+    report,
+    swap,
+    sync_swap,
+    undo_swap
+);
+pub mod close;
+pub mod order;
 pub mod thread;
 
+pub use close::{Close, Tolerance};
+#[cfg(feature = "derive")]
+pub use kai_derive::{Close, Variants};
+pub use order::{NanError, NotNan, OrdF32, OrdF64};
+
 pub use std::{
     cmp::Ordering,
     collections::{HashMap, HashSet},
@@ -105,6 +160,7 @@ Maps an enum to an option for use with `Iterator::filter_map`
 # Syntax
 ```ignore
 variant!( pattern = input => output )
+variant!( pattern | pattern if guard = input => output )
 ```
 
 # Example
@@ -114,6 +170,7 @@ use kai::*;
 enum Foo {
     Bar(bool),
     Baz(i32),
+    Qux(i32),
 }
 
 let bar = Foo::Bar(true);
@@ -147,21 +204,161 @@ let ints: Vec<i32> = foos
     .collect();
 
 assert_eq!(vec![5, 2], ints);
+
+// Or-patterns and guards are supported
+let foos = vec![Foo::Baz(5), Foo::Qux(-1), Foo::Qux(3)];
+let positive: Vec<i32> = foos
+    .iter()
+    .filter_map(|foo| variant!(Foo::Baz(i) | Foo::Qux(i) if *i > 0 = foo => *i))
+    .collect();
+
+assert_eq!(vec![5, 3], positive);
 ```
 */
 #[macro_export]
 macro_rules! variant {
-    ($pattern:pat = $input:expr => $output:expr) => {
-        if let $pattern = $input {
-            Some($output)
-        } else {
-            None
+    // Split the pattern and guard from the input at the first lone `=`
+    (@split $kind:ident [$($head:tt)*] = $input:expr => $($rest:tt)+) => {
+        $crate::variant!(@$kind [$($head)*] ($input) $($rest)+)
+    };
+    (@split $kind:ident [$($head:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::variant!(@split $kind [$($head)* $next] $($rest)*)
+    };
+    (@option [$($pattern:pat)|+ $(if $guard:expr)?] ($input:expr) $output:expr) => {
+        match $input {
+            $($pattern)|+ $(if $guard)? => Some($output),
+            _ => None,
+        }
+    };
+    (@result [$($pattern:pat)|+ $(if $guard:expr)?] ($input:expr) $output:expr) => {
+        match $input {
+            $($pattern)|+ $(if $guard)? => Ok($output),
+            other => Err($crate::VariantError::new(stringify!($($pattern)|+), &other)),
+        }
+    };
+    (@result [$($pattern:pat)|+ $(if $guard:expr)?] ($input:expr) $output:expr, else $other:pat => $error:expr) => {
+        match $input {
+            $($pattern)|+ $(if $guard)? => Ok($output),
+            $other => Err($error),
         }
     };
+    (@borrow [$($head:tt)*] ($input:expr) $output:expr) => {
+        $crate::variant!(@option [$($head)*] (&$input) $output)
+    };
+    (@borrow_mut [$($head:tt)*] ($input:expr) $output:expr) => {
+        $crate::variant!(@option [$($head)*] (&mut $input) $output)
+    };
+    ($($tokens:tt)+) => {
+        $crate::variant!(@split option [] $($tokens)+)
+    };
+}
+
+/**
+Like [`variant!`](macro.variant.html), but returns a `Result` with an error if the pattern does not match
+
+By default, the error is a [`VariantError`](struct.VariantError.html) describing which
+variant was expected and which was found, which requires the input to implement `Debug`.
+A custom error can be made from the unmatched value with `else`.
+
+# Syntax
+```ignore
+variant_or!( pattern = input => output )
+variant_or!( pattern = input => output, else other => error )
+```
+
+# Example
+```
+use kai::*;
+
+#[derive(Debug)]
+enum Shape {
+    Circle(f64),
+    Square(f64),
+}
+
+let square = Shape::Square(2.0);
+
+let error = variant_or!(Shape::Circle(r) = &square => *r).unwrap_err();
+assert_eq!("expected Shape::Circle(r), found Square", error.to_string());
+
+let area = variant_or!(Shape::Square(s) = square => s * s, else other => format!("{:?}", other));
+assert_eq!(Ok(4.0), area);
+```
+*/
+#[macro_export]
+macro_rules! variant_or {
+    ($($tokens:tt)+) => {
+        $crate::variant!(@split result [] $($tokens)+)
+    };
+}
+
+/**
+Like [`variant!`](macro.variant.html), but borrows the input so the output can borrow from it
+
+# Example
+```
+use kai::*;
+
+enum Message {
+    Text(String),
+    Empty,
+}
+
+let message = Message::Text("hi".into());
+let text: Option<&String> = variant_ref!(Message::Text(s) = message => s);
+
+assert_eq!(Some(&"hi".to_string()), text);
+assert_eq!(None, variant_ref!(Message::Text(s) = Message::Empty => s.len()));
+```
+*/
+#[macro_export]
+macro_rules! variant_ref {
+    ($($tokens:tt)+) => {
+        $crate::variant!(@split borrow [] $($tokens)+)
+    };
+}
+
+/**
+Like [`variant!`](macro.variant.html), but mutably borrows the input so the output can mutate it
+
+# Example
+```
+use kai::*;
+
+enum Message {
+    Text(String),
+    Empty,
+}
+
+let mut message = Message::Text("hi".into());
+if let Some(text) = variant_mut!(Message::Text(s) = message => s) {
+    text.push('!');
+}
+
+assert_eq!(Some("hi!"), variant_ref!(Message::Text(s) = message => s.as_str()));
+```
+*/
+#[macro_export]
+macro_rules! variant_mut {
+    ($($tokens:tt)+) => {
+        $crate::variant!(@split borrow_mut [] $($tokens)+)
+    };
 }
 
 /**
-Maps `bool`s to `Option`s in one line
+Maps `bool`s to `Option`s and `Result`s in one line
+
+This trait is sealed. It is implemented for `bool`, `Option<bool>`, where `None` counts as `false`,
+and `AtomicBool`, which is loaded with `SeqCst` ordering.
+
+None of its method names are shared with `Option`, `Iterator`, or `ops::Not`, so they
+can always be called with method syntax. The old `map` and `map_with` names are
+deprecated in favor of [`then_value`](#method.then_value) and [`then_with`](#method.then_with).
+
+Earlier versions implemented this trait for every type that implements `Into<bool>`.
+That blanket implementation could not coexist with the one for `Option<bool>`, so it was removed.
+Other `Into<bool>` types must now be converted with `bool::from` first, which the deprecated
+[`into_bool`](fn.into_bool.html) function also does.
 
 # Example
 ```
@@ -177,41 +374,239 @@ let s = if condition {
 };
 
 // Into this:
-let s = condition.map_with(String::new);
+let s = condition.then_with(String::new);
 
 assert_eq!(Some(String::new()), s);
+
+// Guard checks can be written as single expressions
+fn check_age(age: i32) -> DynResult<()> {
+    (age >= 0).true_or("age is negative")?;
+    Ok(())
+}
+
+assert!(check_age(3).is_ok());
+assert!(check_age(-3).is_err());
+
+// Borrow an `AtomicBool` to load it instead of consuming it
+let ready = std::sync::atomic::AtomicBool::new(true);
+assert_eq!("ready", (&ready).select("ready", "waiting"));
 ```
 */
-pub trait BoolMap {
-    /// Map to an optional value
-    fn map<T>(self, value: T) -> Option<T>;
-    /// Map to an optional value using a function
-    fn map_with<T, F>(self, f: F) -> Option<T>
-    where
-        F: FnMut() -> T;
-}
+pub trait BoolMap: Sized + sealed::Sealed {
+    /// Get the `bool` value
+    fn into_bool(self) -> bool;
+    /**
+    Map to an optional value
 
-impl<B> BoolMap for B
-where
-    B: Into<bool>,
-{
-    fn map<T>(self, value: T) -> Option<T> {
-        if self.into() {
+    # Example
+    ```
+    use kai::*;
+
+    let flag: Option<bool> = Some(true);
+
+    assert_eq!(Some(5), flag.then_value(5));
+    assert_eq!(None, false.then_value(5));
+    ```
+    */
+    fn then_value<T>(self, value: T) -> Option<T> {
+        if self.into_bool() {
             Some(value)
         } else {
             None
         }
     }
-    fn map_with<T, F>(self, mut f: F) -> Option<T>
+    /// Map to an optional value using a function
+    fn then_with<T, F>(self, f: F) -> Option<T>
     where
-        F: FnMut() -> T,
+        F: FnOnce() -> T,
     {
-        if self.into() {
+        if self.into_bool() {
             Some(f())
         } else {
             None
         }
     }
+    /// Map to an optional value
+    #[deprecated(note = "renamed to `then_value` to avoid colliding with `Option::map`")]
+    fn map<T>(self, value: T) -> Option<T> {
+        self.then_value(value)
+    }
+    /// Map to an optional value using a function
+    #[deprecated(note = "renamed to `then_with` to avoid colliding with other `map_with` methods")]
+    fn map_with<T, F>(self, f: F) -> Option<T>
+    where
+        F: FnMut() -> T,
+    {
+        self.then_with(f)
+    }
+    /**
+    Map to `Ok(())` if `true`, otherwise to the given error
+
+    # Example
+    ```
+    use kai::*;
+
+    assert_eq!(Ok(()), true.true_or("error"));
+    assert_eq!(Err("error"), Some(false).true_or("error"));
+    ```
+    */
+    fn true_or<E>(self, err: E) -> Result<(), E> {
+        if self.into_bool() {
+            Ok(())
+        } else {
+            Err(err)
+        }
+    }
+    /**
+    Map to `Ok(())` if `true`, otherwise to an error created by a function
+
+    # Example
+    ```
+    use kai::*;
+
+    let name = "";
+    let result = (!name.is_empty()).true_or_else(|| format!("invalid name {:?}", name));
+
+    assert_eq!(Err("invalid name \"\"".to_string()), result);
+    ```
+    */
+    fn true_or_else<E, F>(self, f: F) -> Result<(), E>
+    where
+        F: FnOnce() -> E,
+    {
+        if self.into_bool() {
+            Ok(())
+        } else {
+            Err(f())
+        }
+    }
+    /**
+    Call a fallible function if `true`
+
+    Returns `Ok(None)` if `false`.
+
+    # Example
+    ```
+    use kai::*;
+
+    let parse = true;
+
+    assert_eq!(Ok(Some(5)), parse.then_try(|| "5".parse::<i32>()));
+    assert!(parse.then_try(|| "five".parse::<i32>()).is_err());
+    assert_eq!(Ok(None), false.then_try(|| "five".parse::<i32>()));
+    ```
+    */
+    fn then_try<T, E, F>(self, f: F) -> Result<Option<T>, E>
+    where
+        F: FnOnce() -> Result<T, E>,
+    {
+        if self.into_bool() {
+            f().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+    /**
+    Choose the first value if `true`, otherwise the second
+
+    # Example
+    ```
+    use kai::*;
+
+    let count = 1;
+
+    assert_eq!("item", (count == 1).select("item", "items"));
+    ```
+    */
+    fn select<T>(self, if_true: T, if_false: T) -> T {
+        if self.into_bool() {
+            if_true
+        } else {
+            if_false
+        }
+    }
+    /**
+    Get the opposite `bool`, so that the other methods apply when the value is `false`
+
+    # Example
+    ```
+    use kai::*;
+
+    let flag: Option<bool> = None;
+
+    assert!(flag.negated());
+    assert_eq!(Err("flag is off"), flag.true_or("flag is off"));
+    assert_eq!(Ok(()), flag.negated().true_or("flag is on"));
+    ```
+    */
+    fn negated(self) -> bool {
+        !self.into_bool()
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for bool {}
+    impl Sealed for Option<bool> {}
+    impl Sealed for std::sync::atomic::AtomicBool {}
+    impl Sealed for &std::sync::atomic::AtomicBool {}
+}
+
+impl BoolMap for bool {
+    fn into_bool(self) -> bool {
+        self
+    }
+}
+
+impl BoolMap for Option<bool> {
+    fn into_bool(self) -> bool {
+        self.unwrap_or(false)
+    }
+}
+
+impl BoolMap for std::sync::atomic::AtomicBool {
+    fn into_bool(self) -> bool {
+        self.into_inner()
+    }
+}
+
+impl BoolMap for &std::sync::atomic::AtomicBool {
+    fn into_bool(self) -> bool {
+        self.load(std::sync::atomic::Ordering::SeqCst)
+    }
+}
+
+/**
+Convert a value to a `bool` so that [`BoolMap`](trait.BoolMap.html) methods can be called on it
+
+This keeps the methods available for `Into<bool>` types, which `BoolMap` used to be implemented for.
+
+# Example
+```
+# #![allow(deprecated)]
+use kai::*;
+
+struct Flag(bool);
+
+impl From<Flag> for bool {
+    fn from(flag: Flag) -> bool {
+        flag.0
+    }
+}
+
+assert_eq!(Some(5), into_bool(Flag(true)).then_value(5));
+assert_eq!(Some(5), bool::from(Flag(true)).then_value(5));
+```
+*/
+#[deprecated(
+    note = "`BoolMap` is no longer implemented for `Into<bool>` types; convert with `bool::from` instead"
+)]
+pub fn into_bool<B>(value: B) -> bool
+where
+    B: Into<bool>,
+{
+    value.into()
 }
 
 /**
@@ -265,243 +660,951 @@ pub trait Bind: Sized {
     {
         f(self)
     }
-}
-
-impl<T> Bind for T {}
+    /**
+    Binds the value, mutates it with a fallible function, and returns it if the function succeeds
 
-/**
-An iterator adaptor created by [`KaiIterator::chain_if`](trait.KaiIterator.html#method.chain_if)
-*/
-pub enum ChainIf<I, J>
-where
-    I: IntoIterator,
-    J: IntoIterator<Item = I::Item>,
-{
-    /// The iterator was chained
-    Chained(I::IntoIter, J::IntoIter),
-    /// The iterator was not chained
-    NotChained(I::IntoIter),
-}
+    # Example
+    ```
+    use kai::*;
 
-impl<I, J> Iterator for ChainIf<I, J>
-where
-    I: IntoIterator,
-    J: IntoIterator<Item = I::Item>,
-{
-    type Item = I::Item;
-    fn next(&mut self) -> Option<Self::Item> {
-        use ChainIf::*;
-        match self {
-            Chained(first, second) => first.next().or_else(|| second.next()),
-            NotChained(iter) => iter.next(),
-        }
+    fn push_digit(v: &mut Vec<u32>, c: char) -> Result<(), String> {
+        v.push(c.to_digit(10).ok_or_else(|| format!("{:?} is not a digit", c))?);
+        Ok(())
     }
-}
 
-/**
-An iterator adaptor created by [`KaiIterator::chain_if_else`](trait.KaiIterator.html#method.chain_if_else)
-*/
-pub enum ChainIfElse<I, J, K>
-where
-    I: IntoIterator,
-    J: IntoIterator<Item = I::Item>,
-    K: IntoIterator<Item = I::Item>,
-{
-    /// The iterator was chained with the first iterator
-    If(I::IntoIter, J::IntoIter),
-    /// The iterator was chained with the second iterator
-    Else(I::IntoIter, K::IntoIter),
-}
+    let v = Vec::new().bind_try_mut(|v| push_digit(v, '4'));
+    assert_eq!(Ok(vec![4]), v);
 
-impl<I, J, K> Iterator for ChainIfElse<I, J, K>
-where
-    I: IntoIterator,
-    J: IntoIterator<Item = I::Item>,
-    K: IntoIterator<Item = I::Item>,
-{
-    type Item = I::Item;
-    fn next(&mut self) -> Option<Self::Item> {
-        use ChainIfElse::*;
-        match self {
-            If(first, second) => first.next().or_else(|| second.next()),
-            Else(first, second) => first.next().or_else(|| second.next()),
-        }
+    let v = Vec::new().bind_try_mut(|v| push_digit(v, 'x'));
+    assert_eq!(Err("'x' is not a digit".to_string()), v);
+    ```
+    */
+    fn bind_try_mut<F, E>(mut self, mut f: F) -> Result<Self, E>
+    where
+        F: FnMut(&mut Self) -> Result<(), E>,
+    {
+        f(&mut self)?;
+        Ok(self)
     }
-}
-
-/**
-Generates my custom iterator adapters
-
-For convenience, this is implmented for all types that
-implement not just [`Iterator`](https://doc.rust-lang.org/std/iter/trait.Iterator.html),
-but [`IntoIterator`](https://doc.rust-lang.org/std/iter/trait.IntoIterator.html) as well.
-
-See methods for usage.
-*/
-pub trait KaiIterator: IntoIterator + Sized {
     /**
-    Chain this iterator with another if the condition is true
+    Binds the value, mutates it if the condition is true, and returns it
 
     # Example
     ```
     use kai::*;
 
-    let condition = true;
-
-    // Turn this
-    let mut v = vec![1, 2, 3];
-    if condition {
-        v.extend(vec![4, 5, 6])
-    }
+    let descending = true;
+    let v = vec![2, 3, 1].bind_mut(|v| v.sort()).bind_mut_if(descending, |v| v.reverse());
 
-    // Into this
+    assert_eq!(vec![3, 2, 1], v);
+    ```
+    */
+    fn bind_mut_if<F>(mut self, condition: bool, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        if condition {
+            f(&mut self);
+        }
+        self
+    }
+    /**
+    Binds the value, passes a reference to it to the function, and returns it
+
+    # Example
+    ```
+    use kai::*;
+
+    let mut lengths = Vec::new();
+    let s = String::from("abc")
+        .bind_inspect(|s| lengths.push(s.len()))
+        .bind_mut(|s| s.push('d'))
+        .bind_inspect(|s| lengths.push(s.len()));
+
+    assert_eq!("abcd", s);
+    assert_eq!(vec![3, 4], lengths);
+    ```
+    */
+    fn bind_inspect<F>(self, mut f: F) -> Self
+    where
+        F: FnMut(&Self),
+    {
+        f(&self);
+        self
+    }
+    /**
+    Binds the value, prints it to stderr with `Debug` and the caller's location like `dbg!`, and returns it
+
+    # Example
+    ```
+    use kai::*;
+
+    // Prints something like:
+    // [src/main.rs:6:43] [
+    //     1,
+    //     2,
+    // ]
+    let v = vec![2, 1].bind_mut(|v| v.sort()).bind_tap_dbg();
+
+    assert_eq!(vec![1, 2], v);
+    ```
+    */
+    #[track_caller]
+    fn bind_tap_dbg(self) -> Self
+    where
+        Self: Debug,
+    {
+        let location = std::panic::Location::caller();
+        eprintln!(
+            "[{}:{}:{}] {:#?}",
+            location.file(),
+            location.line(),
+            location.column(),
+            self
+        );
+        self
+    }
+}
+
+impl<T> Bind for T {}
+
+/**
+Generates my custom iterator adapters
+
+For convenience, this is implmented for all types that
+implement not just [`Iterator`](https://doc.rust-lang.org/std/iter/trait.Iterator.html),
+but [`IntoIterator`](https://doc.rust-lang.org/std/iter/trait.IntoIterator.html) as well.
+
+See methods for usage.
+*/
+pub trait KaiIterator: IntoIterator + Sized {
+    /**
+    Chain this iterator with another if the condition is true
+
+    # Example
+    ```
+    use kai::*;
+
+    let condition = true;
+
+    // Turn this
+    let mut v = vec![1, 2, 3];
+    if condition {
+        v.extend(vec![4, 5, 6])
+    }
+
+    // Into this
     let w: Vec<_> = vec![1, 2, 3].chain_if(condition, || vec![4, 5, 6]).collect();
 
-    assert_eq!(v, w);
+    assert_eq!(v, w);
+    ```
+    */
+    fn chain_if<I, F>(self, condition: bool, f: F) -> ChainIf<Self, I>
+    where
+        I: IntoIterator<Item = Self::Item>,
+        F: FnOnce() -> I,
+    {
+        if condition {
+            ChainIf::Chained(self.into_iter(), f().into_iter())
+        } else {
+            ChainIf::NotChained(self.into_iter())
+        }
+    }
+    /**
+    Chain this iterator with one of two options based on the condition
+
+    If the condition is true, the iterator generated by `f` is used.
+    If the condition is false, the iterator generated by `g` is used.
+
+    # Example
+    ```
+    use kai::*;
+
+    let v = vec![1, 2, 3];
+    let len = v.len();
+    let w: Vec<_> = v.into_iter().chain_if_else(
+        len == 3,
+        || vec![4, 5],
+        || Some(6)
+    ).collect();
+
+    assert_eq!(
+        w,
+        vec![1, 2, 3, 4, 5],
+    );
+    ```
+    */
+    fn chain_if_else<J, K, F, G>(self, condition: bool, f: F, g: G) -> ChainIfElse<Self, J, K>
+    where
+        J: IntoIterator<Item = Self::Item>,
+        K: IntoIterator<Item = Self::Item>,
+        F: FnOnce() -> J,
+        G: FnOnce() -> K,
+    {
+        if condition {
+            ChainIfElse::If(self.into_iter(), f().into_iter())
+        } else {
+            ChainIfElse::Else(self.into_iter(), g().into_iter())
+        }
+    }
+    /**
+    Map each item with a function if the condition is true
+
+    # Example
+    ```
+    use kai::*;
+
+    let double = true;
+
+    // Turn this
+    let v: Vec<i32> = if double {
+        vec![1, 2, 3].into_iter().map(|n| n * 2).collect()
+    } else {
+        vec![1, 2, 3]
+    };
+
+    // Into this
+    let w: Vec<i32> = vec![1, 2, 3].map_if(double, |n| n * 2).collect();
+
+    assert_eq!(v, w);
+    assert_eq!(vec![1, 2, 3], vec![1, 2, 3].map_if(false, |n| n * 2).collect::<Vec<_>>());
+    ```
+    */
+    fn map_if<F>(self, condition: bool, f: F) -> MapIf<Self, F>
+    where
+        F: FnMut(Self::Item) -> Self::Item,
+    {
+        if condition {
+            MapIf::Mapped(self.into_iter(), f)
+        } else {
+            MapIf::NotMapped(self.into_iter())
+        }
+    }
+    /**
+    Filter the items with a predicate if the condition is true
+
+    # Example
+    ```
+    use kai::*;
+
+    let only_even = true;
+    let v: Vec<_> = (1..=6).filter_if(only_even, |n| n % 2 == 0).collect();
+
+    assert_eq!(vec![2, 4, 6], v);
+    ```
+    */
+    fn filter_if<P>(self, condition: bool, predicate: P) -> FilterIf<Self, P>
+    where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        if condition {
+            FilterIf::Filtered(self.into_iter(), predicate)
+        } else {
+            FilterIf::NotFiltered(self.into_iter())
+        }
+    }
+    /**
+    Skip the first `n` items if the condition is true
+
+    # Example
+    ```
+    use kai::*;
+
+    let has_header = true;
+    let lines = vec!["name,age", "Tom,30", "Mary,25"];
+    let rows: Vec<_> = lines.skip_if(has_header, 1).collect();
+
+    assert_eq!(vec!["Tom,30", "Mary,25"], rows);
+    ```
+    */
+    fn skip_if(self, condition: bool, n: usize) -> SkipIf<Self> {
+        if condition {
+            SkipIf::Skipped(self.into_iter(), n)
+        } else {
+            SkipIf::NotSkipped(self.into_iter())
+        }
+    }
+    /**
+    Only take the first `n` items if the condition is true
+
+    This is named `take_first_if` rather than `take_if` so that it does
+    not shadow `Option::take_if` when `kai::*` is imported.
+
+    # Example
+    ```
+    use kai::*;
+
+    let preview = true;
+    let v: Vec<_> = (1..).take_first_if(preview, 3).take(10).collect();
+
+    assert_eq!(vec![1, 2, 3], v);
+
+    // `Option::take_if` is still available
+    let mut n = Some(3);
+    assert_eq!(Some(3), n.take_if(|n| *n > 2));
+    ```
+    */
+    fn take_first_if(self, condition: bool, n: usize) -> TakeFirstIf<Self> {
+        if condition {
+            TakeFirstIf::Taken(self.into_iter(), n)
+        } else {
+            TakeFirstIf::NotTaken(self.into_iter())
+        }
+    }
+    /**
+    Reverse the iterator if the condition is true
+
+    # Example
+    ```
+    use kai::*;
+
+    let descending = true;
+    let v: Vec<_> = vec![1, 2, 3].rev_if(descending).collect();
+
+    assert_eq!(vec![3, 2, 1], v);
+    ```
+    */
+    fn rev_if(self, condition: bool) -> RevIf<Self>
+    where
+        Self::IntoIter: DoubleEndedIterator,
+    {
+        if condition {
+            RevIf::Reversed(self.into_iter())
+        } else {
+            RevIf::NotReversed(self.into_iter())
+        }
+    }
+    /**
+    Call a function on each item if the condition is true
+
+    # Example
+    ```
+    use kai::*;
+
+    let verbose = true;
+    let mut seen = Vec::new();
+    let sum: i32 = vec![1, 2, 3].inspect_if(verbose, |n| seen.push(*n)).sum();
+
+    assert_eq!(6, sum);
+    assert_eq!(vec![1, 2, 3], seen);
+    ```
+    */
+    fn inspect_if<F>(self, condition: bool, f: F) -> InspectIf<Self, F>
+    where
+        F: FnMut(&Self::Item),
+    {
+        if condition {
+            InspectIf::Inspected(self.into_iter(), f)
+        } else {
+            InspectIf::NotInspected(self.into_iter())
+        }
+    }
+    /**
+    Transform the iterator into the left side of an [`Either`](enum.Either.html) if the condition is true
+
+    If the condition is false, the untransformed iterator is the right side.
+    This allows any transformation to be applied conditionally without boxing.
+
+    # Example
+    ```
+    use kai::*;
+
+    let step = true;
+    let v: Vec<_> = (0..10).left_if(step, |iter| iter.step_by(3)).collect();
+
+    assert_eq!(vec![0, 3, 6, 9], v);
+    ```
+    */
+    fn left_if<L, F>(self, condition: bool, f: F) -> Either<L, Self::IntoIter>
+    where
+        L: Iterator<Item = Self::Item>,
+        F: FnOnce(Self::IntoIter) -> L,
+    {
+        if condition {
+            Either::Left(f(self.into_iter()))
+        } else {
+            Either::Right(self.into_iter())
+        }
+    }
+    /**
+    Transform the iterator into the right side of an [`Either`](enum.Either.html) if the condition is true
+
+    If the condition is false, the untransformed iterator is the left side.
+
+    # Example
+    ```
+    use kai::*;
+
+    let cycle = false;
+    let v: Vec<_> = vec![1, 2].right_if(cycle, |iter| iter.cycle().take(6)).collect();
+
+    assert_eq!(vec![1, 2], v);
+    ```
+    */
+    fn right_if<R, F>(self, condition: bool, f: F) -> Either<Self::IntoIter, R>
+    where
+        R: Iterator<Item = Self::Item>,
+        F: FnOnce(Self::IntoIter) -> R,
+    {
+        if condition {
+            Either::Right(f(self.into_iter()))
+        } else {
+            Either::Left(self.into_iter())
+        }
+    }
+    /**
+    Group consecutive items that share the same key
+
+    Each group is yielded along with its key. Unlike a `HashMap`-based grouping,
+    items with the same key that are not adjacent end up in separate groups.
+
+    # Example
+    ```
+    use kai::*;
+
+    let groups: Vec<_> = vec![1, 3, 2, 4, 5].group_by_key(|n| n % 2).collect();
+
+    assert_eq!(vec![(1, vec![1, 3]), (0, vec![2, 4]), (1, vec![5])], groups);
+    ```
+    */
+    fn group_by_key<F, K>(self, f: F) -> GroupByKey<Self::IntoIter, F, K>
+    where
+        F: FnMut(&Self::Item) -> K,
+        K: PartialEq,
+    {
+        GroupByKey::new(self.into_iter(), f)
+    }
+    /**
+    Split the iterator into runs where the predicate holds for each pair of adjacent items
+
+    This is named `into_chunk_by` rather than `chunk_by` so that it does not
+    shadow `slice::chunk_by` on `Vec`s when `kai::*` is imported.
+
+    # Example
+    ```
+    use kai::*;
+
+    let runs: Vec<_> = vec![1, 2, 3, 7, 8, 10].into_chunk_by(|a, b| a + 1 == *b).collect();
+
+    assert_eq!(vec![vec![1, 2, 3], vec![7, 8], vec![10]], runs);
+    ```
+    */
+    fn into_chunk_by<P>(self, predicate: P) -> IntoChunkBy<Self::IntoIter, P>
+    where
+        P: FnMut(&Self::Item, &Self::Item) -> bool,
+    {
+        IntoChunkBy::new(self.into_iter(), predicate)
+    }
+    /**
+    Collect the items into `Vec`s of `size` items
+
+    The last chunk may be shorter. This is named `into_chunks` rather than `chunks`
+    so that it does not shadow `slice::chunks` on `Vec`s when `kai::*` is imported.
+
+    # Panics
+    Panics if `size` is 0
+
+    # Example
+    ```
+    use kai::*;
+
+    let chunks: Vec<_> = (1..=5).into_chunks(2).collect();
+
+    assert_eq!(vec![vec![1, 2], vec![3, 4], vec![5]], chunks);
+    ```
+    */
+    fn into_chunks(self, size: usize) -> IntoChunks<Self::IntoIter> {
+        IntoChunks::new(self.into_iter(), size)
+    }
+    /**
+    Collect overlapping windows of `size` items into `Vec`s
+
+    If there are fewer than `size` items, no windows are yielded. This is named
+    `into_windows` rather than `windows` so that it does not shadow `slice::windows`
+    on `Vec`s when `kai::*` is imported.
+
+    # Panics
+    Panics if `size` is 0
+
+    # Example
+    ```
+    use kai::*;
+
+    let windows: Vec<_> = (1..=4).into_windows(3).collect();
+
+    assert_eq!(vec![vec![1, 2, 3], vec![2, 3, 4]], windows);
+    assert_eq!(0, (1..=2).into_windows(3).count());
+    ```
+    */
+    fn into_windows(self, size: usize) -> IntoWindows<Self::IntoIter>
+    where
+        Self::Item: Clone,
+    {
+        IntoWindows::new(self.into_iter(), size)
+    }
+    /**
+    Alternate between the items of this iterator and another
+
+    Once either iterator runs out, the rest of the other one is yielded.
+
+    # Example
+    ```
+    use kai::*;
+
+    let v: Vec<_> = vec![1, 3, 5, 7].interleave(vec![2, 4]).collect();
+
+    assert_eq!(vec![1, 2, 3, 4, 5, 7], v);
+    ```
+    */
+    fn interleave<J>(self, other: J) -> Interleave<Self::IntoIter, J::IntoIter>
+    where
+        J: IntoIterator<Item = Self::Item>,
+    {
+        Interleave::new(self.into_iter(), other.into_iter())
+    }
+    /**
+    Place a clone of the separator between each pair of adjacent items
+
+    This is named `into_intersperse` rather than `intersperse` so that it does
+    not collide with the unstable `Iterator::intersperse`.
+
+    # Example
+    ```
+    use kai::*;
+
+    let s: String = vec!["a", "b", "c"].into_intersperse(", ").collect();
+
+    assert_eq!("a, b, c", s);
+    ```
+    */
+    fn into_intersperse(self, separator: Self::Item) -> IntoIntersperse<Self::IntoIter>
+    where
+        Self::Item: Clone,
+    {
+        IntoIntersperse::new(self.into_iter(), separator)
+    }
+    /**
+    Skip items whose key is equal to the key of the previous yielded item
+
+    This is named `into_dedup_by_key` rather than `dedup_by_key` so that it does
+    not shadow `Vec::dedup_by_key` when `kai::*` is imported.
+
+    # Example
+    ```
+    use kai::*;
+
+    let v: Vec<_> = vec![10, 11, 20, 12, 25].into_dedup_by_key(|n| n / 10).collect();
+
+    assert_eq!(vec![10, 20, 12, 25], v);
+    ```
+    */
+    fn into_dedup_by_key<F, K>(self, f: F) -> IntoDedupByKey<Self::IntoIter, F, K>
+    where
+        F: FnMut(&Self::Item) -> K,
+        K: PartialEq,
+    {
+        IntoDedupByKey::new(self.into_iter(), f)
+    }
+    /**
+    Collect and sort the items with a comparator, such as one from [`order`](order/index.html)
+
+    # Example
+    ```
+    use kai::*;
+
+    let v: Vec<_> = vec![3.0, 1.0, 2.0].sorted_by(order::or_less).collect();
+
+    assert_eq!(vec![1.0, 2.0, 3.0], v);
+    ```
+    */
+    fn sorted_by<C>(self, cmp: C) -> std::vec::IntoIter<Self::Item>
+    where
+        C: FnMut(&Self::Item, &Self::Item) -> Ordering,
+    {
+        let mut items: Vec<Self::Item> = self.into_iter().collect();
+        items.sort_by(cmp);
+        items.into_iter()
+    }
+    /**
+    Collect the `Ok` values of an iterator of `Result`s, or every error if there are any
+
+    Unlike collecting into a `Result`, this does not stop at the first error.
+
+    # Example
+    ```
+    use kai::*;
+
+    let all: Result<Vec<i32>, _> = vec!["1", "2"].into_iter().map(str::parse::<i32>).collect_all_errors();
+    assert_eq!(vec![1, 2], all.unwrap());
+
+    let errors = vec!["1", "a", "b"]
+        .into_iter()
+        .map(str::parse::<i32>)
+        .collect_all_errors::<Vec<_>, _, _>()
+        .unwrap_err();
+    assert_eq!(2, errors.len());
     ```
     */
-    fn chain_if<I, F>(self, condition: bool, f: F) -> ChainIf<Self, I>
+    fn collect_all_errors<C, T, E>(self) -> Result<C, Errors<E>>
     where
-        I: IntoIterator<Item = Self::Item>,
-        F: FnOnce() -> I,
+        Self: IntoIterator<Item = Result<T, E>>,
+        C: iter::FromIterator<T>,
     {
-        if condition {
-            ChainIf::Chained(self.into_iter(), f().into_iter())
+        let mut errors = Vec::new();
+        let collection = self
+            .into_iter()
+            .filter_map(|result| result.map_err(|e| errors.push(e)).ok())
+            .collect();
+        if errors.is_empty() {
+            Ok(collection)
         } else {
-            ChainIf::NotChained(self.into_iter())
+            Err(Errors::new(errors))
         }
     }
     /**
-    Chain this iterator with one of two options based on the condition
+    Split an iterator of `Result`s into the `Ok` values and the errors
 
-    If the condition is true, the iterator generated by `f` is used.
-    If the condition is false, the iterator generated by `g` is used.
+    # Example
+    ```
+    use kai::*;
+
+    let (oks, errs) = vec![Ok(1), Err("a"), Ok(2), Err("b")].partition_results();
+
+    assert_eq!(vec![1, 2], oks);
+    assert_eq!(vec!["a", "b"], errs);
+    ```
+    */
+    fn partition_results<T, E>(self) -> (Vec<T>, Vec<E>)
+    where
+        Self: IntoIterator<Item = Result<T, E>>,
+    {
+        let mut oks = Vec::new();
+        let mut errs = Vec::new();
+        for result in self {
+            match result {
+                Ok(t) => oks.push(t),
+                Err(e) => errs.push(e),
+            }
+        }
+        (oks, errs)
+    }
+    /**
+    Call a fallible function on every item, continuing after errors
+
+    Returns every error if there were any.
 
     # Example
     ```
     use kai::*;
 
-    let v = vec![1, 2, 3];
-    let len = v.len();
-    let w: Vec<_> = v.into_iter().chain_if_else(
-        len == 3,
-        || vec![4, 5],
-        || Some(6)
-    ).collect();
+    let mut total = 0;
+    let result = vec![1, -2, 3, -4].try_for_each_all(|n| {
+        if n < 0 {
+            return Err(format!("{} is negative", n));
+        }
+        total += n;
+        Ok(())
+    });
 
-    assert_eq!(
-        w,
-        vec![1, 2, 3, 4, 5],
-    );
+    assert_eq!(4, total);
+    assert_eq!(vec!["-2 is negative", "-4 is negative"], result.unwrap_err().into_vec());
     ```
     */
-    fn chain_if_else<J, K, F, G>(self, condition: bool, f: F, g: G) -> ChainIfElse<Self, J, K>
+    fn try_for_each_all<F, E>(self, mut f: F) -> Result<(), Errors<E>>
     where
-        J: IntoIterator<Item = Self::Item>,
-        K: IntoIterator<Item = Self::Item>,
-        F: FnOnce() -> J,
-        G: FnOnce() -> K,
+        F: FnMut(Self::Item) -> Result<(), E>,
     {
-        if condition {
-            ChainIfElse::If(self.into_iter(), f().into_iter())
+        let errors: Vec<E> = self.into_iter().filter_map(|item| f(item).err()).collect();
+        if errors.is_empty() {
+            Ok(())
         } else {
-            ChainIfElse::Else(self.into_iter(), g().into_iter())
+            Err(Errors::new(errors))
         }
     }
-}
+    /**
+    Get the minimum float, using a comparator from [`order`](order/index.html) to decide where NaNs go
 
-impl<I> KaiIterator for I where I: IntoIterator + Sized {}
+    Returns `None` if the iterator is empty.
 
-/**
-An dynamic `Result` type
-*/
-pub type DynResult<T> = Result<T, Box<dyn Error>>;
+    # Example
+    ```
+    use kai::*;
 
-/**
-An alias for `io::Result`
-*/
-pub type IoResult<T> = std::io::Result<T>;
+    let v = vec![2.0, f64::NAN, -1.0];
 
-/**
-An alias for `fmt::Result`
-*/
-pub type FmtResult = std::fmt::Result;
+    assert_eq!(Some(-1.0), v.iter().copied().fmin(order::nan_last));
+    assert!(v.iter().copied().fmin(order::nan_first).unwrap().is_nan());
+    assert_eq!(None, Vec::<f64>::new().fmin(order::nan_last));
+    ```
+    */
+    fn fmin<C>(self, cmp: C) -> Option<Self::Item>
+    where
+        C: Fn(&Self::Item, &Self::Item) -> Ordering,
+    {
+        self.into_iter().min_by(cmp)
+    }
+    /**
+    Get the maximum float, using a comparator from [`order`](order/index.html) to decide where NaNs go
 
-/**
-Functions for fully ordering `PartialOrd` types
+    Returns `None` if the iterator is empty.
 
-These functions are intended for use with certain standard library
-functions that take a `Fn(&T, &T) -> Ordering` to order items, such as
-`Iterator::max_by`, `Iterator::min_by`, and `Vec::sort_by`.
+    # Example
+    ```
+    use kai::*;
 
-# Example
-```
-use kai::*;
+    let v = vec![2.0, f64::NAN, -1.0];
+
+    assert_eq!(Some(2.0), v.iter().copied().fmax(order::nan_first));
+    assert!(v.iter().copied().fmax(order::nan_last).unwrap().is_nan());
+    ```
+    */
+    fn fmax<C>(self, cmp: C) -> Option<Self::Item>
+    where
+        C: Fn(&Self::Item, &Self::Item) -> Ordering,
+    {
+        self.into_iter().max_by(cmp)
+    }
+    /**
+    Get the minimum and maximum floats in a single pass,
+    using a comparator from [`order`](order/index.html) to decide where NaNs go
 
-let mut v: Vec<f32> = vec![1.0, 0.1, -4.1, 5.2];
+    Returns `None` if the iterator is empty.
 
-v.sort_by(order::or_less);
-let max = *v.iter().max_by(order::or_greater).unwrap();
+    # Example
+    ```
+    use kai::*;
 
-assert_eq!(
-    vec![-4.1, 0.1, 1.0, 5.2],
-    v
-);
-assert_eq!(5.2, max);
-```
-*/
-pub mod order {
-    use std::cmp::Ordering;
-    /// Order and use `Ordering::Less` as a default
-    pub fn or_less<T>(a: &T, b: &T) -> Ordering
+    assert_eq!(Some((-1.0, 3.0)), vec![2.0, 3.0, -1.0].minmax(order::nan_first));
+    assert_eq!(Some((1.0, 1.0)), Some(1.0).minmax(order::nan_first));
+    assert_eq!(None, None::<f32>.minmax(order::nan_first));
+    ```
+    */
+    fn minmax<C>(self, cmp: C) -> Option<(Self::Item, Self::Item)>
     where
-        T: PartialOrd,
+        Self::Item: Clone,
+        C: Fn(&Self::Item, &Self::Item) -> Ordering,
     {
-        a.partial_cmp(&b).unwrap_or(Ordering::Less)
+        let mut iter = self.into_iter();
+        let first = iter.next()?;
+        Some(iter.fold((first.clone(), first), |(min, max), item| {
+            if cmp(&item, &min) == Ordering::Less {
+                (item, max)
+            } else if cmp(&item, &max) != Ordering::Less {
+                (min, item)
+            } else {
+                (min, max)
+            }
+        }))
     }
-    /// Order and use `Ordering::Greater` as a default
-    pub fn or_greater<T>(a: &T, b: &T) -> Ordering
+    /**
+    Sum floats using Neumaier's compensated summation
+
+    This is much more accurate than adding the floats one at a time.
+    NaNs and infinities propagate as they would in normal addition.
+    The sum of an empty iterator is `0.0`.
+
+    # Example
+    ```
+    use kai::*;
+
+    let v = vec![1.0, 1e100, 1.0, -1e100];
+
+    assert_eq!(0.0, v.iter().sum::<f64>());
+    assert_eq!(2.0, v.fsum());
+    ```
+    */
+    fn fsum(self) -> f64
     where
-        T: PartialOrd,
+        Self::Item: Into<f64>,
     {
-        a.partial_cmp(&b).unwrap_or(Ordering::Greater)
+        neumaier_sum(self).0
     }
-    /// Order and use `Ordering::Equal` as a default
-    pub fn or_equal<T>(a: &T, b: &T) -> Ordering
+    /**
+    Get the mean of some floats
+
+    The floats are summed with [`fsum`](trait.KaiIterator.html#method.fsum).
+    Returns `None` if the iterator is empty.
+
+    # Example
+    ```
+    use kai::*;
+
+    assert_eq!(Some(2.5), vec![1, 2, 3, 4].mean());
+    assert_eq!(None, Vec::<f32>::new().mean());
+    ```
+    */
+    fn mean(self) -> Option<f64>
     where
-        T: PartialOrd,
+        Self::Item: Into<f64>,
     {
-        a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+        let (sum, count) = neumaier_sum(self);
+        if count == 0 {
+            None
+        } else {
+            Some(sum / count as f64)
+        }
     }
-}
+    /**
+    Get the population variance of some floats using Welford's algorithm
 
-/**
-Functions for checking if two floating-point numbers are close enough to be considered equal
+    Returns `None` if the iterator is empty.
 
-These functions use the `std::f**::EPSILON` constants to check if two numbers are close
-enough for their difference to be the result of rounding errors. I made these primarily to
-get clippy off my back about directly comparing floats.
-*/
-pub mod close {
-    #![allow(clippy::trivially_copy_pass_by_ref)]
-    /// Check if two `f32`s are close enough to be considered equal
-    pub fn f32(a: f32, b: f32) -> bool {
-        (a - b).abs() < std::f32::EPSILON
+    # Example
+    ```
+    use kai::*;
+
+    let v = vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+
+    assert_eq!(Some(4.0), v.variance());
+    assert_eq!(None, Vec::<f64>::new().variance());
+    ```
+    */
+    fn variance(self) -> Option<f64>
+    where
+        Self::Item: Into<f64>,
+    {
+        let (count, _, m2) = welford(self);
+        if count == 0 {
+            None
+        } else {
+            Some(m2 / count as f64)
+        }
+    }
+    /**
+    Get the sample variance of some floats using Welford's algorithm
+
+    Returns `None` if the iterator has fewer than two items.
+
+    # Example
+    ```
+    use kai::*;
+
+    assert_eq!(Some(2.5), vec![1.0, 2.0, 3.0, 4.0, 5.0].sample_variance());
+    assert_eq!(None, vec![1.0].sample_variance());
+    ```
+    */
+    fn sample_variance(self) -> Option<f64>
+    where
+        Self::Item: Into<f64>,
+    {
+        let (count, _, m2) = welford(self);
+        if count < 2 {
+            None
+        } else {
+            Some(m2 / (count - 1) as f64)
+        }
+    }
+    /**
+    Get the median of some floats, using a comparator from [`order`](order/index.html) to decide where NaNs go
+
+    If there are an even number of floats, the mean of the middle two is returned.
+    Returns `None` if the iterator is empty.
+
+    # Example
+    ```
+    use kai::*;
+
+    assert_eq!(Some(2.0), vec![3.0, 1.0, 2.0].median(order::nan_last));
+    assert_eq!(Some(2.5), vec![4.0, 1.0, 3.0, 2.0].median(order::nan_last));
+    // With `nan_last`, NaN counts as the largest value
+    assert_eq!(Some(3.0), vec![f64::NAN, 1.0, 3.0].median(order::nan_last));
+    assert_eq!(None, Vec::<f64>::new().median(order::nan_last));
+    ```
+    */
+    fn median<C>(self, cmp: C) -> Option<f64>
+    where
+        Self::Item: Into<f64>,
+        C: Fn(&Self::Item, &Self::Item) -> Ordering,
+    {
+        let mut items: Vec<Self::Item> = self.into_iter().collect();
+        items.sort_by(cmp);
+        let mid = items.len() / 2;
+        if items.is_empty() {
+            None
+        } else if items.len() % 2 == 1 {
+            Some(items.swap_remove(mid).into())
+        } else {
+            let high = items.swap_remove(mid).into();
+            let low = items.swap_remove(mid - 1).into();
+            Some(low / 2.0 + high / 2.0)
+        }
     }
-    /// Check if two `&f32`s are close enough to be considered equal
-    pub fn f32_ref(a: &f32, b: &f32) -> bool {
-        (*a - *b).abs() < std::f32::EPSILON
+}
+
+impl<I> KaiIterator for I where I: IntoIterator + Sized {}
+
+/// Sum with Neumaier's algorithm, returning the sum and the number of items
+fn neumaier_sum<I>(iter: I) -> (f64, usize)
+where
+    I: IntoIterator,
+    I::Item: Into<f64>,
+{
+    let mut sum = 0.0;
+    let mut compensation = 0.0;
+    let mut count = 0;
+    for x in iter {
+        let x: f64 = x.into();
+        let t = sum + x;
+        if f64::abs(sum) >= x.abs() {
+            compensation += (sum - t) + x;
+        } else {
+            compensation += (x - t) + sum;
+        }
+        sum = t;
+        count += 1;
     }
-    /// Check if two `f64`s are close enough to be considered equal
-    pub fn f64(a: f64, b: f64) -> bool {
-        (a - b).abs() < std::f64::EPSILON
+    // Infinities make the compensation NaN
+    if sum.is_finite() {
+        (sum + compensation, count)
+    } else {
+        (sum, count)
     }
-    /// Check if two `&f64`s are close enough to be considered equal
-    pub fn f64_ref(a: &f64, b: &f64) -> bool {
-        (*a - *b).abs() < std::f64::EPSILON
+}
+
+/// Run Welford's algorithm, returning the count, the mean, and the sum of squared differences from the mean
+fn welford<I>(iter: I) -> (usize, f64, f64)
+where
+    I: IntoIterator,
+    I::Item: Into<f64>,
+{
+    let mut count = 0;
+    let mut mean = 0.0;
+    let mut m2 = 0.0;
+    for x in iter {
+        let x: f64 = x.into();
+        count += 1;
+        let delta = x - mean;
+        mean += delta / count as f64;
+        m2 += delta * (x - mean);
     }
+    (count, mean, m2)
 }
 
+/**
+An dynamic `Result` type
+*/
+pub type DynResult<T> = Result<T, Box<dyn Error>>;
+
+/**
+A dynamic `Result` type whose error can be sent between threads
+*/
+pub type SyncDynResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+/**
+A `Result` whose error is a [`ContextError`](struct.ContextError.html)
+*/
+pub type ContextResult<T> = Result<T, ContextError>;
+
+/**
+An alias for `io::Result`
+*/
+pub type IoResult<T> = std::io::Result<T>;
+
+/**
+An alias for `fmt::Result`
+*/
+pub type FmtResult = std::fmt::Result;
+
 /**
 Temporarily gain access to an immutable reference as mutable
 
@@ -513,6 +1616,10 @@ that could go wrong, so this function is still marked `unsafe`. If you are someo
 knows the specific ways that using this function could still cause undefined behvaior,
 please let me know by emailing me or opening an issue.
 
+# Safety
+
+The caller must ensure that no other references to `var` are used while `f` is running.
+
 # Example
 ```
 use kai::*;
@@ -568,3 +1675,149 @@ macro_rules! cond_vec {
         new_vec
     }};
 }
+
+/**
+Pipes a value through a series of functions, reading left to right
+
+Each stage expands to a call to [`Bind::bind_map`](trait.Bind.html#method.bind_map).
+
+# Syntax
+```ignore
+pipe!( value => stage => stage ... )
+```
+
+A stage can be:
+* Any expression that evaluates to a function, such as a path or a closure
+* A function call where `_` placeholders stand for the value, like `f(_, 2)` or `Vec::<u8>::with_capacity(_)`
+* A method call on the value, like `.len()` or `.parse::<i32>()`
+
+A stage followed by `?` propagates errors like the `?` operator.
+Because stages are run in closures, `?` cannot be used inside a stage.
+Placeholders are only replaced where a whole argument is `_`, so `_` can still be used
+as an inferred type inside an argument, like in `f(_, 2u8 as _)`.
+
+# Example
+```
+use kai::*;
+
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+let x = pipe!(2i32.pow(3) => |x| x / 3 => add(_, 5) => i32::pow(_, 2) => .to_string());
+
+assert_eq!("49", x);
+
+// A call without placeholders must evaluate to a function
+fn adder(n: i32) -> impl Fn(i32) -> i32 {
+    move |x| x + n
+}
+
+assert_eq!(3, pipe!(1 => adder(2)));
+
+// Paths and methods can take generic arguments
+let v = pipe!(4 => Vec::<u8>::with_capacity(_) => .capacity());
+assert!(v >= 4);
+assert_eq!(Ok(42), pipe!("42" => .parse::<i32>()));
+assert_eq!(vec![2, 4, 6], pipe!(1..4 => .map(|n| n * 2) => .collect::<Vec<_>>()));
+
+// Only a whole argument is a placeholder, not an inferred type
+fn add_u8(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+fn push_all(n: u8, mut v: Vec<u8>) -> Vec<u8> {
+    v.push(n);
+    v
+}
+
+assert_eq!(5, pipe!(3 => add_u8(_, 2u16 as _)));
+assert_eq!(vec![3], pipe!(3 => push_all(_, Vec::<_>::new())));
+
+fn parse_sum(a: &str, b: &str) -> Result<i32, std::num::ParseIntError> {
+    let b = b.parse()?;
+    Ok(pipe!(a => str::parse::<i32>? => add(_, b)))
+}
+
+assert_eq!(Ok(5), parse_sum("2", "3"));
+assert!(parse_sum("2", "three").is_err());
+```
+*/
+#[macro_export]
+macro_rules! pipe {
+    // Replace arguments that are a single `_` with the value, leaving `_` types alone
+    (@args $kind:ident $x:ident [$($callee:tt)*] [$($done:tt)*] $found:ident [] _ , $($rest:tt)*) => {
+        $crate::pipe!(@args $kind $x [$($callee)*] [$($done)* $x ,] found [] $($rest)*)
+    };
+    (@args $kind:ident $x:ident [$($callee:tt)*] [$($done:tt)*] $found:ident [] _) => {
+        $crate::pipe!(@args $kind $x [$($callee)*] [$($done)* $x] found [])
+    };
+    // Split the arguments on commas
+    (@args $kind:ident $x:ident [$($callee:tt)*] [$($done:tt)*] $found:ident [$($arg:tt)*] , $($rest:tt)*) => {
+        $crate::pipe!(@args $kind $x [$($callee)*] [$($done)* $($arg)* ,] $found [] $($rest)*)
+    };
+    (@args $kind:ident $x:ident [$($callee:tt)*] [$($done:tt)*] $found:ident [$($arg:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::pipe!(@args $kind $x [$($callee)*] [$($done)*] $found [$($arg)* $next] $($rest)*)
+    };
+    // A function call without placeholders evaluates to the function to call
+    (@args call $x:ident [$($callee:tt)*] [$($done:tt)*] none [$($arg:tt)*]) => {
+        ($($callee)*($($done)* $($arg)*))($x)
+    };
+    (@args $kind:ident $x:ident [$($callee:tt)*] [$($done:tt)*] $found:ident [$($arg:tt)*]) => {
+        $($callee)*($($done)* $($arg)*)
+    };
+    // Collect a method, including any turbofish, up to its arguments
+    (@method $value:expr; [$($method:tt)+] ( $($args:tt)* )) => {
+        $crate::Bind::bind_map($value, |value| {
+            $crate::pipe!(@args method value [value.$($method)+] [] none [] $($args)*)
+        })
+    };
+    (@method $value:expr; [$($method:tt)+] $next:tt $($rest:tt)+) => {
+        $crate::pipe!(@method $value; [$($method)+ $next] $($rest)+)
+    };
+    // Collect a path, including any generic arguments, up to its call arguments
+    (@path $value:expr; [$($f:tt)+] ( $($args:tt)* )) => {
+        $crate::Bind::bind_map($value, |value| {
+            $crate::pipe!(@args call value [$($f)+] [] none [] $($args)*)
+        })
+    };
+    (@path $value:expr; [$($f:tt)+] $next:tt $($rest:tt)*) => {
+        $crate::pipe!(@path $value; [$($f)+ $next] $($rest)*)
+    };
+    // The stage does not end with a call, so it is the function itself
+    (@path $value:expr; [$($f:tt)+]) => {
+        $crate::Bind::bind_map($value, $($f)+)
+    };
+    // Apply a single stage
+    (@apply $value:expr; . $method:ident $($rest:tt)+) => {
+        $crate::pipe!(@method $value; [$method] $($rest)+)
+    };
+    (@apply $value:expr; $f:ident $($rest:tt)*) => {
+        $crate::pipe!(@path $value; [$f] $($rest)*)
+    };
+    (@apply $value:expr; $f:expr) => {
+        $crate::Bind::bind_map($value, $f)
+    };
+    // Split the stages on `=>`
+    (@stages ($value:expr) [$($stage:tt)+] ? => $($rest:tt)+) => {
+        $crate::pipe!(@stages ($crate::pipe!(@apply $value; $($stage)+)?) [] $($rest)+)
+    };
+    (@stages ($value:expr) [$($stage:tt)+] ?) => {
+        $crate::pipe!(@apply $value; $($stage)+)?
+    };
+    (@stages ($value:expr) [$($stage:tt)+] => $($rest:tt)+) => {
+        $crate::pipe!(@stages ($crate::pipe!(@apply $value; $($stage)+)) [] $($rest)+)
+    };
+    (@stages ($value:expr) [$($stage:tt)+]) => {
+        $crate::pipe!(@apply $value; $($stage)+)
+    };
+    (@stages ($value:expr) [$($stage:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::pipe!(@stages ($value) [$($stage)* $next] $($rest)*)
+    };
+    ($value:expr => $($rest:tt)+) => {
+        $crate::pipe!(@stages ($value) [] $($rest)+)
+    };
+    ($value:expr $(,)?) => {
+        $value
+    };
+}
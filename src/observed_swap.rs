@@ -0,0 +1,226 @@
+use super::*;
+
+type Observer<T> = Box<dyn FnMut(&T, Option<&T>)>;
+
+/**
+Wrapper that allows consuming transformations on borrowed data
+and notifies observers of every change
+
+Observers are called with the new value after every
+[`hold`](struct.ObservedSwap.html#method.hold) and after every write through a
+[`SwapGuard`](struct.SwapGuard.html). The old value is also passed to observers
+when the value is transformed with [`hold_tracked`](struct.ObservedSwap.html#method.hold_tracked).
+
+Because observers must be notified after every mutation, `ObservedSwap` does not implement `DerefMut`.
+Use [`write`](struct.ObservedSwap.html#method.write) instead.
+
+# Example
+```
+use kai::*;
+
+let renders = Rc::new(std::cell::RefCell::new(Vec::new()));
+let renders_clone = Rc::clone(&renders);
+
+let mut state = ObservedSwap::new(vec![1, 2, 3]);
+state.observe(move |new: &Vec<i32>, old: Option<&Vec<i32>>| {
+    renders_clone.borrow_mut().push((new.len(), old.map(Vec::len)))
+});
+
+state.hold(|v| v.into_iter().filter(|n| n % 2 == 1).collect());
+state.hold_tracked(|v| v.into_iter().chain(Some(5)).collect());
+state.write().push(7);
+
+assert_eq!(vec![1, 3, 5, 7], *state);
+assert_eq!(
+    vec![(2, None), (3, Some(2)), (4, None)],
+    *renders.borrow()
+);
+```
+*/
+pub struct ObservedSwap<T> {
+    inner: Swap<T>,
+    observers: Vec<Observer<T>>,
+}
+
+impl<T> ObservedSwap<T> {
+    /// Create a new `ObservedSwap` with no observers
+    pub fn new(inner: T) -> Self {
+        ObservedSwap::from(inner)
+    }
+    /// Register a function to be called after every change
+    ///
+    /// The function receives the new value and, if available, the old value.
+    pub fn observe<F>(&mut self, f: F)
+    where
+        F: FnMut(&T, Option<&T>) + 'static,
+    {
+        self.observers.push(Box::new(f));
+    }
+    /// Remove all observers
+    pub fn clear_observers(&mut self) {
+        self.observers.clear();
+    }
+    /// Take the inner value, transform it, put it back in place,
+    /// and notify observers of the new value
+    pub fn hold<F>(&mut self, f: F)
+    where
+        F: FnOnce(T) -> T,
+    {
+        self.inner.hold(f);
+        self.notify(None);
+    }
+    /// Take the inner value, transform it, put it back in place,
+    /// and notify observers of both the new and old values
+    pub fn hold_tracked<F>(&mut self, f: F)
+    where
+        T: Clone,
+        F: FnOnce(T) -> T,
+    {
+        let old = T::clone(&self.inner);
+        self.inner.hold(f);
+        self.notify(Some(&old));
+    }
+    /// Get mutable access to the inner value
+    ///
+    /// Observers are notified of the new value when the returned guard is dropped.
+    pub fn write(&mut self) -> SwapGuard<'_, T> {
+        SwapGuard { swap: self }
+    }
+    /// Take the inner value
+    pub fn into_inner(self) -> T {
+        self.inner.into_inner()
+    }
+    fn notify(&mut self, old: Option<&T>) {
+        for observer in &mut self.observers {
+            observer(&self.inner, old);
+        }
+    }
+}
+
+impl<T> Deref for ObservedSwap<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T> From<T> for ObservedSwap<T> {
+    fn from(inner: T) -> Self {
+        ObservedSwap {
+            inner: Swap::from(inner),
+            observers: Vec::new(),
+        }
+    }
+}
+
+impl<T> Default for ObservedSwap<T>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        ObservedSwap::from(T::default())
+    }
+}
+
+impl<T> Debug for ObservedSwap<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        <T as Debug>::fmt(&self.inner, f)
+    }
+}
+
+impl<T> Display for ObservedSwap<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        <T as Display>::fmt(&self.inner, f)
+    }
+}
+
+impl<T> AsRef<T> for ObservedSwap<T> {
+    fn as_ref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T> std::borrow::Borrow<T> for ObservedSwap<T> {
+    fn borrow(&self) -> &T {
+        &self.inner
+    }
+}
+
+/**
+Mutable access to the value of an [`ObservedSwap`](struct.ObservedSwap.html)
+
+Observers are notified when the guard is dropped, unless it is dropped
+because of a panic. A value left behind by a panicking write is not reported,
+and an observer panicking during unwinding would abort the process.
+
+# Example
+```
+use kai::*;
+
+let notified = Rc::new(std::cell::Cell::new(0));
+let notified_clone = Rc::clone(&notified);
+
+let mut count = ObservedSwap::new(0);
+count.observe(move |_: &i32, _| notified_clone.set(notified_clone.get() + 1));
+
+*count.write() += 1;
+assert_eq!(1, notified.get());
+
+let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+    let mut guard = count.write();
+    *guard += 1;
+    panic!("write failed");
+}));
+assert!(result.is_err());
+assert_eq!(1, notified.get());
+```
+*/
+pub struct SwapGuard<'a, T> {
+    swap: &'a mut ObservedSwap<T>,
+}
+
+impl<'a, T> SwapGuard<'a, T> {
+    /**
+    Force a drop, notifying observers
+
+    This function only needs to be called if you want the observers to be notified
+    before the `SwapGuard` would normally be dropped.
+    */
+    pub fn finish(self) {}
+}
+
+impl<'a, T> Deref for SwapGuard<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.swap.inner
+    }
+}
+
+impl<'a, T> DerefMut for SwapGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.swap.inner
+    }
+}
+
+impl<'a, T> Drop for SwapGuard<'a, T> {
+    fn drop(&mut self) {
+        if !std::thread::panicking() {
+            self.swap.notify(None)
+        }
+    }
+}
+
+impl<'a, T> Debug for SwapGuard<'a, T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        <T as Debug>::fmt(&self.swap.inner, f)
+    }
+}
@@ -0,0 +1,656 @@
+/*!
+Functions for fully ordering `PartialOrd` types
+
+These functions are intended for use with certain standard library
+functions that take a `Fn(&T, &T) -> Ordering` to order items, such as
+`Iterator::max_by`, `Iterator::min_by`, and `Vec::sort_by`.
+
+The `or_*` functions do not produce a consistent order when NaN is present,
+because NaN is less than, greater than, or equal to every number depending on
+which side of the comparison it is on. [`nan_first`](fn.nan_first.html),
+[`nan_last`](fn.nan_last.html), and [`nan_error`](fn.nan_error.html) explicitly
+state where NaNs go and are always consistent.
+
+Comparators can be combined with [`by_key`](fn.by_key.html), [`by_ref_key`](fn.by_ref_key.html),
+and [`reverse`](fn.reverse.html),
+and sorting by several keys can be done with a [`Comparator`](struct.Comparator.html).
+
+This module also contains float wrappers that implement `Ord`, `Eq`, and `Hash`,
+so they can be sorted directly and used as `HashMap` and `BTreeMap` keys.
+* [`OrdF32`](struct.OrdF32.html) and [`OrdF64`](struct.OrdF64.html) order floats according to the IEEE 754 totalOrder predicate
+* [`NotNan`](struct.NotNan.html) rejects NaN and orders floats numerically
+
+# Example
+```
+use kai::*;
+
+let mut v: Vec<f32> = vec![1.0, 0.1, -4.1, 5.2];
+
+v.sort_by(order::or_less);
+let max = *v.iter().max_by(order::or_greater).unwrap();
+
+assert_eq!(
+    vec![-4.1, 0.1, 1.0, 5.2],
+    v
+);
+assert_eq!(5.2, max);
+
+let mut v = vec![2.0, f64::NAN, -1.0, 0.5];
+v.sort_by(order::nan_first);
+assert!(v[0].is_nan());
+assert_eq!(vec![-1.0, 0.5, 2.0], v[1..].to_vec());
+
+v.sort_by(order::reverse(order::nan_last));
+assert!(v[0].is_nan());
+assert_eq!(vec![2.0, 0.5, -1.0], v[1..].to_vec());
+
+let mut people = vec![("Tom", 1.8), ("Mary", 1.6), ("Dan", f64::NAN)];
+people.sort_by(order::by_key(|p: &(&str, f64)| p.1, order::nan_last));
+assert_eq!(vec!["Mary", "Tom", "Dan"], people.iter().map(|p| p.0).collect::<Vec<_>>());
+```
+*/
+
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    error::Error,
+    fmt::{self, Debug, Display, Formatter},
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+/// Order and use `Ordering::Less` as a default
+pub fn or_less<T>(a: &T, b: &T) -> Ordering
+where
+    T: PartialOrd,
+{
+    a.partial_cmp(b).unwrap_or(Ordering::Less)
+}
+/// Order and use `Ordering::Greater` as a default
+pub fn or_greater<T>(a: &T, b: &T) -> Ordering
+where
+    T: PartialOrd,
+{
+    a.partial_cmp(b).unwrap_or(Ordering::Greater)
+}
+/// Order and use `Ordering::Equal` as a default
+pub fn or_equal<T>(a: &T, b: &T) -> Ordering
+where
+    T: PartialOrd,
+{
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+/// Check if a value is NaN, or more generally, if it cannot be compared with itself
+fn is_nan<T>(a: &T) -> bool
+where
+    T: PartialOrd,
+{
+    a.partial_cmp(a).is_none()
+}
+
+/**
+Order with NaNs before all other values
+
+All NaNs are considered equal. Other incomparable values are considered equal.
+*/
+pub fn nan_first<T>(a: &T, b: &T) -> Ordering
+where
+    T: PartialOrd,
+{
+    match (is_nan(a), is_nan(b)) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => or_equal(a, b),
+    }
+}
+/**
+Order with NaNs after all other values
+
+All NaNs are considered equal. Other incomparable values are considered equal.
+*/
+pub fn nan_last<T>(a: &T, b: &T) -> Ordering
+where
+    T: PartialOrd,
+{
+    match (is_nan(a), is_nan(b)) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => or_equal(a, b),
+    }
+}
+/**
+Order, failing if either value is NaN
+
+# Example
+```
+use kai::*;
+
+assert_eq!(Ok(Ordering::Less), order::nan_error(&1.0, &2.0));
+assert_eq!(Err(NanError), order::nan_error(&1.0, &f32::NAN));
+```
+*/
+pub fn nan_error<T>(a: &T, b: &T) -> Result<Ordering, NanError>
+where
+    T: PartialOrd,
+{
+    if is_nan(a) || is_nan(b) {
+        Err(NanError)
+    } else {
+        Ok(or_equal(a, b))
+    }
+}
+
+/// Order values by comparing the keys produced by a function
+pub fn by_key<T, K, F, C>(f: F, cmp: C) -> impl Fn(&T, &T) -> Ordering
+where
+    F: Fn(&T) -> K,
+    C: Fn(&K, &K) -> Ordering,
+{
+    move |a: &T, b: &T| cmp(&f(a), &f(b))
+}
+
+/**
+Order values by comparing keys borrowed from them
+
+Unlike [`by_key`](fn.by_key.html), the key function returns a reference into the value,
+so keys such as `String` fields can be compared without being cloned.
+
+# Example
+```
+use kai::*;
+
+let mut words = vec![(2, "b".to_string()), (1, "c".to_string()), (3, "a".to_string())];
+words.sort_by(order::by_ref_key(|w: &(i32, String)| w.1.as_str(), Ord::cmp));
+assert_eq!(vec![3, 2, 1], words.iter().map(|w| w.0).collect::<Vec<_>>());
+```
+*/
+pub fn by_ref_key<T, K, F, C>(f: F, cmp: C) -> impl Fn(&T, &T) -> Ordering
+where
+    T: ?Sized,
+    K: ?Sized,
+    F: Fn(&T) -> &K,
+    C: Fn(&K, &K) -> Ordering,
+{
+    move |a: &T, b: &T| cmp(f(a), f(b))
+}
+
+/// Reverse the order of a comparator
+pub fn reverse<T, C>(cmp: C) -> impl Fn(&T, &T) -> Ordering
+where
+    C: Fn(&T, &T) -> Ordering,
+{
+    move |a: &T, b: &T| cmp(a, b).reverse()
+}
+
+/**
+A lexicographic comparator built from several keys
+
+Values are compared by each key in the order the keys were added.
+Later keys are only compared if all earlier keys are equal.
+
+Keys added with [`asc_ref`](struct.Comparator.html#method.asc_ref) and
+[`desc_ref`](struct.Comparator.html#method.desc_ref) are borrowed from the values,
+as with [`by_ref_key`](fn.by_ref_key.html).
+Each key is compared with its own comparator, such as [`or_less`](fn.or_less.html),
+[`or_greater`](fn.or_greater.html), [`or_equal`](fn.or_equal.html), or [`nan_last`](fn.nan_last.html).
+A key added with [`desc`](struct.Comparator.html#method.desc) reverses its entire comparator,
+so NaNs placed first by `nan_first` end up last.
+
+# Example
+```
+use kai::*;
+
+struct Run {
+    score: f64,
+    time: f64,
+    name: String,
+}
+
+let run = |score, time, name: &str| Run { score, time, name: name.into() };
+let mut runs = vec![
+    run(10.0, 3.0, "c"),
+    run(20.0, f64::NAN, "a"),
+    run(10.0, 2.0, "d"),
+    run(20.0, 1.0, "b"),
+    run(10.0, 2.0, "b"),
+];
+
+// Sort by score descending, then time ascending with NaN last, then name
+let cmp = order::Comparator::new()
+    .desc(|run: &Run| run.score, order::or_equal)
+    .asc(|run: &Run| run.time, order::nan_last)
+    .asc_ref(|run: &Run| run.name.as_str(), Ord::cmp);
+
+runs.sort_by(|a, b| cmp.compare(a, b));
+let names: Vec<_> = runs.iter().map(|run| run.name.as_str()).collect();
+assert_eq!(vec!["b", "a", "b", "d", "c"], names);
+
+// A `Comparator` can also be turned into a plain function
+let cmp = cmp.build();
+let best = runs.iter().min_by(&cmp).unwrap();
+assert_eq!(1.0, best.time);
+let worst = runs.iter().max_by(&cmp).unwrap();
+assert_eq!("c", worst.name);
+```
+*/
+pub struct Comparator<'a, T> {
+    keys: Vec<KeyComparator<'a, T>>,
+}
+
+type KeyComparator<'a, T> = Box<dyn Fn(&T, &T) -> Ordering + 'a>;
+
+impl<'a, T> Comparator<'a, T> {
+    /// Create a new `Comparator` with no keys, which considers all values equal
+    pub fn new() -> Self {
+        Comparator { keys: Vec::new() }
+    }
+    /// Add a comparator that compares whole values
+    pub fn then<C>(mut self, cmp: C) -> Self
+    where
+        C: Fn(&T, &T) -> Ordering + 'a,
+    {
+        self.keys.push(Box::new(cmp));
+        self
+    }
+    /// Add a key that is sorted in ascending order by the given comparator
+    pub fn asc<K, F, C>(self, key: F, cmp: C) -> Self
+    where
+        T: 'a,
+        K: 'a,
+        F: Fn(&T) -> K + 'a,
+        C: Fn(&K, &K) -> Ordering + 'a,
+    {
+        self.then(by_key(key, cmp))
+    }
+    /// Add a key that is sorted in descending order by the given comparator
+    pub fn desc<K, F, C>(self, key: F, cmp: C) -> Self
+    where
+        T: 'a,
+        K: 'a,
+        F: Fn(&T) -> K + 'a,
+        C: Fn(&K, &K) -> Ordering + 'a,
+    {
+        self.then(reverse(by_key(key, cmp)))
+    }
+    /// Add a key borrowed from the value that is sorted in ascending order by the given comparator
+    pub fn asc_ref<K, F, C>(self, key: F, cmp: C) -> Self
+    where
+        T: 'a,
+        K: ?Sized + 'a,
+        F: Fn(&T) -> &K + 'a,
+        C: Fn(&K, &K) -> Ordering + 'a,
+    {
+        self.then(by_ref_key(key, cmp))
+    }
+    /// Add a key borrowed from the value that is sorted in descending order by the given comparator
+    pub fn desc_ref<K, F, C>(self, key: F, cmp: C) -> Self
+    where
+        T: 'a,
+        K: ?Sized + 'a,
+        F: Fn(&T) -> &K + 'a,
+        C: Fn(&K, &K) -> Ordering + 'a,
+    {
+        self.then(reverse(by_ref_key(key, cmp)))
+    }
+    /// Compare two values by each key in turn
+    pub fn compare(&self, a: &T, b: &T) -> Ordering {
+        self.keys
+            .iter()
+            .map(|cmp| cmp(a, b))
+            .find(|&ordering| ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }
+    /**
+    Turn this `Comparator` into a function that can be passed directly to functions like `sort_by`
+
+    The function compares anything that borrows as a `T`, so it also works with
+    `max_by` and `min_by` on iterators of `&T`.
+
+    # Example
+    ```
+    use kai::*;
+
+    let by_len = || order::Comparator::new().asc(|s: &String| s.len(), Ord::cmp);
+
+    let mut words = vec!["ccc".to_string(), "a".into(), "bb".into()];
+    words.sort_by(by_len().build());
+    assert_eq!(vec!["a", "bb", "ccc"], words);
+    assert_eq!("ccc", words.iter().max_by(by_len().build()).unwrap());
+    assert_eq!("a", words.into_iter().min_by(by_len().build()).unwrap());
+    ```
+    */
+    pub fn build<B>(self) -> impl Fn(&B, &B) -> Ordering + 'a
+    where
+        T: 'a,
+        B: Borrow<T> + ?Sized,
+    {
+        move |a: &B, b: &B| self.compare(a.borrow(), b.borrow())
+    }
+}
+
+impl<'a, T> Default for Comparator<'a, T> {
+    fn default() -> Self {
+        Comparator::new()
+    }
+}
+
+/**
+Sort a slice of floats, failing if any of them are NaN
+
+If the slice contains NaN, it is left unchanged.
+
+# Example
+```
+use kai::*;
+
+let mut v = vec![3.0, 1.0, 2.0];
+assert_eq!(Ok(()), order::sort_floats(&mut v));
+assert_eq!(vec![1.0, 2.0, 3.0], v);
+
+let mut v = vec![3.0, f32::NAN, 2.0];
+assert_eq!(Err(NanError), order::sort_floats(&mut v));
+```
+*/
+pub fn sort_floats<T>(slice: &mut [T]) -> Result<(), NanError>
+where
+    T: PartialOrd,
+{
+    if slice.iter().any(is_nan) {
+        Err(NanError)
+    } else {
+        slice.sort_by(or_equal);
+        Ok(())
+    }
+}
+
+/**
+Get the maximum of a slice of floats
+
+Returns `None` if the slice is empty or if any of the floats are NaN.
+
+# Example
+```
+use kai::*;
+
+assert_eq!(Some(&3.0), order::max_float(&[1.0, 3.0, 2.0]));
+assert_eq!(None, order::max_float(&[1.0, f64::NAN, 2.0]));
+assert_eq!(None, order::max_float::<f64>(&[]));
+```
+*/
+pub fn max_float<T>(slice: &[T]) -> Option<&T>
+where
+    T: PartialOrd,
+{
+    if slice.iter().any(is_nan) {
+        None
+    } else {
+        slice.iter().max_by(or_equal)
+    }
+}
+
+/**
+Get the minimum of a slice of floats
+
+Returns `None` if the slice is empty or if any of the floats are NaN.
+
+# Example
+```
+use kai::*;
+
+assert_eq!(Some(&1.0), order::min_float(&[1.0, 3.0, 2.0]));
+assert_eq!(None, order::min_float(&[1.0, f64::NAN, 2.0]));
+```
+*/
+pub fn min_float<T>(slice: &[T]) -> Option<&T>
+where
+    T: PartialOrd,
+{
+    if slice.iter().any(is_nan) {
+        None
+    } else {
+        slice.iter().min_by(or_equal)
+    }
+}
+
+macro_rules! ord_float {
+    ($(#[$attr:meta])* $name:ident, $float:ty) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Default)]
+        pub struct $name(pub $float);
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.cmp(other) == Ordering::Equal
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $name {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.total_cmp(&other.0)
+            }
+        }
+
+        impl Hash for $name {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.0.to_bits().hash(state)
+            }
+        }
+
+        impl From<$float> for $name {
+            fn from(f: $float) -> Self {
+                $name(f)
+            }
+        }
+
+        impl From<$name> for $float {
+            fn from(f: $name) -> Self {
+                f.0
+            }
+        }
+
+        impl Deref for $name {
+            type Target = $float;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl Debug for $name {
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                <$float as Debug>::fmt(&self.0, f)
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                <$float as Display>::fmt(&self.0, f)
+            }
+        }
+    };
+}
+
+ord_float!(
+    /**
+    An `f32` that is totally ordered according to the IEEE 754 totalOrder predicate
+
+    Negative NaNs are less than everything, positive NaNs are greater than everything,
+    and `-0.0` is less than `0.0`. Two `OrdF32`s are only equal if they have the same bits.
+
+    # Example
+    ```
+    use kai::*;
+
+    let mut v = vec![OrdF32(1.0), OrdF32(f32::NAN), OrdF32(-0.0), OrdF32(0.0), OrdF32(-2.0)];
+    v.sort();
+    let v: Vec<f32> = v.into_iter().map(f32::from).collect();
+    assert_eq!(format!("{:?}", v), "[-2.0, -0.0, 0.0, 1.0, NaN]");
+
+    let mut counts = HashMap::new();
+    *counts.entry(OrdF32(0.5)).or_insert(0) += 1;
+    *counts.entry(OrdF32(0.5)).or_insert(0) += 1;
+    assert_eq!(2, counts[&OrdF32(0.5)]);
+    ```
+    */
+    OrdF32,
+    f32
+);
+
+ord_float!(
+    /**
+    An `f64` that is totally ordered according to the IEEE 754 totalOrder predicate
+
+    Negative NaNs are less than everything, positive NaNs are greater than everything,
+    and `-0.0` is less than `0.0`. Two `OrdF64`s are only equal if they have the same bits.
+
+    # Example
+    ```
+    use kai::*;
+    use std::collections::BTreeMap;
+
+    let mut map = BTreeMap::new();
+    map.insert(OrdF64(2.5), "b");
+    map.insert(OrdF64(-1.0), "a");
+    map.insert(OrdF64(f64::NAN), "c");
+    assert_eq!(vec!["a", "b", "c"], map.values().cloned().collect::<Vec<_>>());
+    ```
+    */
+    OrdF64,
+    f64
+);
+
+/**
+An error returned when trying to create a [`NotNan`](struct.NotNan.html) from NaN
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NanError;
+
+impl Display for NanError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "float is NaN")
+    }
+}
+
+impl Error for NanError {}
+
+/**
+A float that is guaranteed not to be NaN
+
+Because it can never be NaN, a `NotNan` is totally ordered by its numeric value.
+Unlike [`OrdF32`](struct.OrdF32.html) and [`OrdF64`](struct.OrdF64.html),
+`-0.0` and `0.0` are considered equal.
+
+`NotNan` can be created from either `f32` or `f64`.
+
+# Example
+```
+use kai::*;
+
+let mut v = vec![
+    NotNan::new(3.0).unwrap(),
+    NotNan::new(-1.0).unwrap(),
+    NotNan::new(2.0).unwrap(),
+];
+v.sort();
+assert_eq!(-1.0, *v[0]);
+assert_eq!(Some(&NotNan::new(3.0).unwrap()), v.iter().max());
+
+assert_eq!(NotNan::new(0.0f32), NotNan::new(-0.0));
+assert_eq!(Err(NanError), NotNan::new(f32::NAN));
+```
+*/
+#[derive(Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct NotNan<T>(T);
+
+impl<T> NotNan<T>
+where
+    T: PartialEq,
+{
+    /// Create a new `NotNan`, failing if the float is NaN
+    #[allow(clippy::eq_op)]
+    pub fn new(f: T) -> Result<Self, NanError> {
+        // NaN is the only float that is not equal to itself
+        if f != f {
+            Err(NanError)
+        } else {
+            Ok(NotNan(f))
+        }
+    }
+}
+
+impl<T> NotNan<T> {
+    /// Take the inner float
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for NotNan<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> Debug for NotNan<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        <T as Debug>::fmt(&self.0, f)
+    }
+}
+
+impl<T> Display for NotNan<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        <T as Display>::fmt(&self.0, f)
+    }
+}
+
+macro_rules! not_nan {
+    ($float:ty) => {
+        impl Eq for NotNan<$float> {}
+
+        impl Ord for NotNan<$float> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                or_equal(&self.0, &other.0)
+            }
+        }
+
+        impl Hash for NotNan<$float> {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                // `-0.0 + 0.0` is `0.0`, so both zeros hash the same
+                (self.0 + 0.0).to_bits().hash(state)
+            }
+        }
+
+        impl std::convert::TryFrom<$float> for NotNan<$float> {
+            type Error = NanError;
+            fn try_from(f: $float) -> Result<Self, Self::Error> {
+                NotNan::new(f)
+            }
+        }
+
+        impl From<NotNan<$float>> for $float {
+            fn from(f: NotNan<$float>) -> Self {
+                f.0
+            }
+        }
+    };
+}
+
+not_nan!(f32);
+not_nan!(f64);
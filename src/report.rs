@@ -0,0 +1,170 @@
+use super::*;
+
+use std::{backtrace::Backtrace, process::ExitCode};
+
+/**
+An error that can be reported by [`run`](fn.run.html)
+
+Implement this for your own error types to choose which exit code each error produces.
+It is already implemented for `Box<dyn Error>`, `Box<dyn Error + Send + Sync>`,
+and both kinds of [`ContextError`](struct.ContextError.html), which all exit with code 1.
+
+# Example
+```
+use kai::*;
+use std::process::ExitCode;
+
+#[derive(Debug)]
+enum CliError {
+    Usage,
+    Io(std::io::Error),
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            CliError::Usage => write!(f, "invalid usage"),
+            CliError::Io(_) => write!(f, "i/o failed"),
+        }
+    }
+}
+
+impl Error for CliError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        variant!(CliError::Io(e) = self => e as &(dyn Error + 'static))
+    }
+}
+
+impl Report for CliError {
+    fn error(&self) -> &(dyn Error + 'static) {
+        self
+    }
+    fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Usage => 64,
+            CliError::Io(_) => 74,
+        }
+    }
+}
+
+assert_eq!(ExitCode::from(64), run(|| Err(CliError::Usage)));
+```
+*/
+pub trait Report {
+    /// Get the error to print
+    fn error(&self) -> &(dyn Error + 'static);
+    /// Get the process exit code for the error
+    fn exit_code(&self) -> u8 {
+        1
+    }
+    /**
+    Get a backtrace to print
+
+    By default, this is the backtrace of the first [`ContextError`](struct.ContextError.html)
+    in the chain that captured one.
+    */
+    fn backtrace(&self) -> Option<&Backtrace> {
+        iter::successors(Some(self.error()), |&error| error.source())
+            .filter_map(context::ContextParts::of)
+            .find_map(|parts| parts.backtrace)
+    }
+}
+
+impl Report for Box<dyn Error> {
+    fn error(&self) -> &(dyn Error + 'static) {
+        self.as_ref()
+    }
+}
+
+impl Report for Box<dyn Error + Send + Sync> {
+    fn error(&self) -> &(dyn Error + 'static) {
+        self.as_ref()
+    }
+}
+
+impl Report for ContextError {
+    fn error(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
+
+impl Report for ContextError<dyn Error> {
+    fn error(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
+
+/**
+Run a program's main logic, reporting any error
+
+This is meant to be called from `main`. If the function returns an error,
+the error and each of its causes are printed to stderr, and the returned
+`ExitCode` comes from [`Report::exit_code`](trait.Report.html#method.exit_code).
+
+If the error captured a backtrace, which [`ContextError`](struct.ContextError.html)s do
+when the `RUST_BACKTRACE` environment variable is set, it is printed as well.
+
+# Example
+```
+use kai::*;
+use std::process::ExitCode;
+
+// In a real program, this would be `main`
+fn app() -> ExitCode {
+    run(|| -> DynResult<()> {
+        let n = "forty".parse::<i32>().context("parsing the argument")?;
+        println!("{}", n);
+        Ok(())
+    })
+}
+
+// Prints:
+// Error: parsing the argument
+//
+// Caused by:
+//     invalid digit found in string
+assert_eq!(ExitCode::FAILURE, app());
+```
+*/
+pub fn run<F, E>(f: F) -> ExitCode
+where
+    F: FnOnce() -> Result<(), E>,
+    E: Report,
+{
+    match f() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(report) => {
+            eprintln!("{}", ReportDisplay(&report));
+            ExitCode::from(report.exit_code())
+        }
+    }
+}
+
+struct ReportDisplay<'a, R>(&'a R);
+
+impl<'a, R> Display for ReportDisplay<'a, R>
+where
+    R: Report,
+{
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let mut chain = iter::successors(Some(self.0.error()), |&error| error.source());
+        write!(f, "Error: ")?;
+        context::fmt_link(chain.next().unwrap(), f)?;
+        let causes: Vec<_> = chain.collect();
+        if !causes.is_empty() {
+            write!(f, "\n\nCaused by:")?;
+            for (i, cause) in causes.iter().enumerate() {
+                if causes.len() == 1 {
+                    write!(f, "\n    ")?;
+                } else {
+                    write!(f, "\n    {}: ", i)?;
+                }
+                context::fmt_link(*cause, f)?;
+            }
+        }
+        if let Some(backtrace) = self.0.backtrace() {
+            write!(f, "\n\nStack backtrace:\n{}", backtrace)?;
+        }
+        Ok(())
+    }
+}
@@ -103,3 +103,29 @@ impl<T> std::borrow::Borrow<T> for Swap<T> {
         self.0.as_ref().unwrap()
     }
 }
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for Swap<T>
+where
+    T: serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        T::serialize(self.0.as_ref().unwrap(), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for Swap<T>
+where
+    T: serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(Swap::from)
+    }
+}
@@ -0,0 +1,157 @@
+use super::*;
+
+use std::sync::{PoisonError, RwLock};
+
+/**
+Thread-safe wrapper that allows consuming transformations on shared data
+
+This is the thread-safe equivalent of [`Swap`](struct.Swap.html).
+Each call to [`hold`](struct.SyncSwap.html#method.hold) is applied atomically,
+and readers get consistent snapshots of the value in the form of `Arc`s.
+Snapshots are never affected by later transformations.
+
+Transformations work on a clone of the value, so readers are never blocked
+while a transformation runs, and a panicking transformation leaves the value unchanged.
+
+# Example
+```
+use kai::*;
+
+let config = Arc::new(SyncSwap::new(vec![1, 2, 3]));
+
+// Take a snapshot of the current value
+let before = config.load();
+
+// Transform the value from another thread
+let reloader = Arc::clone(&config);
+thread::spawn(move || reloader.hold(|v| v.into_iter().map(|n| n * 2).collect()))
+    .join()
+    .unwrap();
+
+assert_eq!(vec![1, 2, 3], *before);
+assert_eq!(vec![2, 4, 6], *config.load());
+
+// Only transform the value if it has not changed since the snapshot was taken
+assert!(config.compare_and_hold(&before, |v| v).is_err());
+let current = config.load();
+assert!(config.compare_and_hold(&current, |mut v| { v.push(8); v }).is_ok());
+assert_eq!(vec![2, 4, 6, 8], *config.load());
+```
+*/
+pub struct SyncSwap<T> {
+    value: RwLock<Arc<T>>,
+    writer: Mutex<()>,
+}
+
+impl<T> SyncSwap<T> {
+    /// Create a new `SyncSwap`
+    pub fn new(inner: T) -> Self {
+        SyncSwap::from(inner)
+    }
+    /// Get a snapshot of the current value
+    pub fn load(&self) -> Arc<T> {
+        Arc::clone(&self.value.read().unwrap_or_else(PoisonError::into_inner))
+    }
+    /// Replace the value, returning a snapshot of the old one
+    pub fn store(&self, inner: T) -> Arc<T> {
+        let _writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        self.replace(Arc::new(inner))
+    }
+    /// Take the inner value
+    pub fn into_inner(self) -> T
+    where
+        T: Clone,
+    {
+        let inner = self.value.into_inner().unwrap_or_else(PoisonError::into_inner);
+        Arc::try_unwrap(inner).unwrap_or_else(|arc| T::clone(&arc))
+    }
+    /**
+    Transform a clone of the value and put the result in its place
+
+    No other thread can transform or store the value while `f` is running,
+    but other threads can still read it. If `f` panics, the value is unchanged.
+
+    # Example
+    ```
+    use kai::*;
+
+    let swap = SyncSwap::new(1);
+    let result = std::panic::catch_unwind(|| swap.hold(|_| panic!("reload failed")));
+
+    assert!(result.is_err());
+    assert_eq!(1, *swap.load());
+    swap.hold(|n| n + 1);
+    assert_eq!(2, *swap.load());
+    ```
+    */
+    pub fn hold<F>(&self, f: F)
+    where
+        T: Clone,
+        F: FnOnce(T) -> T,
+    {
+        let _writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        let res = f(T::clone(&self.load()));
+        self.replace(Arc::new(res));
+    }
+    /**
+    Transform a clone of the value and put the result in its place,
+    but only if it is the same value as the `current` snapshot
+
+    If the value has been replaced since `current` was loaded,
+    `f` is not called and a snapshot of the actual value is returned.
+    */
+    pub fn compare_and_hold<F>(&self, current: &Arc<T>, f: F) -> Result<(), Arc<T>>
+    where
+        T: Clone,
+        F: FnOnce(T) -> T,
+    {
+        let _writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        let inner = self.load();
+        if !Arc::ptr_eq(&inner, current) {
+            return Err(inner);
+        }
+        let res = f(T::clone(&inner));
+        self.replace(Arc::new(res));
+        Ok(())
+    }
+    fn replace(&self, inner: Arc<T>) -> Arc<T> {
+        let mut value = self.value.write().unwrap_or_else(PoisonError::into_inner);
+        std::mem::replace(&mut *value, inner)
+    }
+}
+
+impl<T> From<T> for SyncSwap<T> {
+    fn from(inner: T) -> Self {
+        SyncSwap {
+            value: RwLock::new(Arc::new(inner)),
+            writer: Mutex::new(()),
+        }
+    }
+}
+
+impl<T> Default for SyncSwap<T>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        SyncSwap::from(T::default())
+    }
+}
+
+impl<T> Debug for SyncSwap<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        <T as Debug>::fmt(&self.load(), f)
+    }
+}
+
+impl<T> Display for SyncSwap<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        <T as Display>::fmt(&self.load(), f)
+    }
+}
@@ -14,17 +14,23 @@ poll its status.
 ```
 use kai::thread;
 
-// Spawn a new smart thread that sleeps for a short amount of time
-let handle = thread::spawn_smart(|| thread::sleep(std::time::Duration::from_millis(10)));
+// Spawn a new smart thread that waits until it is told to finish
+let (sender, receiver) = std::sync::mpsc::channel();
+let handle = thread::spawn_smart(move || receiver.recv().unwrap());
 assert!(handle.status().is_running());
+sender.send(()).unwrap();
 // Wait for the thread to finish
-thread::sleep(std::time::Duration::from_millis(20));
+while handle.status().is_running() {
+    thread::yield_now();
+}
 assert!(handle.status().finished());
 
 // Spawn a new smart thread that panics
 let handle = thread::spawn_smart(|| panic!());
-// Give the thread time to panic
-thread::sleep(std::time::Duration::from_millis(10));
+// Wait for the thread to panic
+while handle.status().is_running() {
+    thread::yield_now();
+}
 assert!(handle.status().panicked());
 ```
 */
@@ -35,6 +41,7 @@ pub use std::thread::*;
 
 /// The execution status of a thread
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ThreadStatus {
     /// The thread is running
     Running,
@@ -0,0 +1,184 @@
+use super::*;
+
+use std::collections::VecDeque;
+
+/**
+What an [`UndoSwap`](struct.UndoSwap.html) does with its history when it reaches its capacity
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum UndoEviction {
+    /// Forget the oldest value to make room for the new one
+    #[default]
+    Oldest,
+    /// Forget the entire history and start over
+    Clear,
+}
+
+/**
+Wrapper that allows consuming transformations on borrowed data
+and keeps a bounded history of replaced values
+
+Every call to [`hold`](struct.UndoSwap.html#method.hold) records the value it replaced.
+Transformations can then be undone and redone. Transforming the value after undoing
+discards the values that could have been redone.
+
+# Example
+```
+use kai::*;
+
+let mut doc = UndoSwap::new(String::from("Hello"), 2);
+
+doc.hold(|s| s + ",");
+doc.hold(|s| s + " world");
+doc.hold(|s| s + "!");
+assert_eq!("Hello, world!", *doc);
+// The oldest value was evicted
+assert_eq!(2, doc.history_len());
+
+assert!(doc.undo());
+assert!(doc.undo());
+assert_eq!("Hello,", *doc);
+assert!(!doc.undo());
+
+assert!(doc.redo());
+assert_eq!("Hello, world", *doc);
+
+// Holding after undoing discards the redo history
+doc.hold(|s| s + "?");
+assert!(!doc.redo());
+assert_eq!("Hello, world?", *doc);
+```
+*/
+#[derive(Clone)]
+pub struct UndoSwap<T> {
+    current: Swap<T>,
+    undo: VecDeque<T>,
+    redo: Vec<T>,
+    capacity: usize,
+    eviction: UndoEviction,
+}
+
+impl<T> UndoSwap<T> {
+    /// Create a new `UndoSwap` that remembers at most `capacity` values
+    pub fn new(inner: T, capacity: usize) -> Self {
+        UndoSwap {
+            current: Swap::new(inner),
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            capacity,
+            eviction: UndoEviction::default(),
+        }
+    }
+    /// Set the eviction policy used when the history is full
+    pub fn with_eviction(self, eviction: UndoEviction) -> Self {
+        UndoSwap { eviction, ..self }
+    }
+    /// Take the inner value, transform it, and put it back in place,
+    /// recording the old value in the history
+    pub fn hold<F>(&mut self, f: F)
+    where
+        T: Clone,
+        F: FnOnce(T) -> T,
+    {
+        let old = T::clone(&self.current);
+        self.current.hold(f);
+        self.redo.clear();
+        self.record(old);
+    }
+    /// Revert to the previous value in the history
+    ///
+    /// Returns `false` if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        if let Some(prev) = self.undo.pop_back() {
+            let current = std::mem::replace(&mut *self.current, prev);
+            self.redo.push(current);
+            true
+        } else {
+            false
+        }
+    }
+    /// Reapply the last undone transformation
+    ///
+    /// Returns `false` if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        if let Some(next) = self.redo.pop() {
+            let current = std::mem::replace(&mut *self.current, next);
+            self.record(current);
+            true
+        } else {
+            false
+        }
+    }
+    /// Get the number of values that can be undone
+    pub fn history_len(&self) -> usize {
+        self.undo.len()
+    }
+    /// Get the number of values that can be redone
+    pub fn redo_len(&self) -> usize {
+        self.redo.len()
+    }
+    /// Get the maximum number of values that are remembered
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    /// Forget all undo and redo history
+    pub fn clear_history(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+    /// Take the inner value
+    pub fn into_inner(self) -> T {
+        self.current.into_inner()
+    }
+    fn record(&mut self, value: T) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.undo.len() == self.capacity {
+            match self.eviction {
+                UndoEviction::Oldest => {
+                    self.undo.pop_front();
+                }
+                UndoEviction::Clear => self.undo.clear(),
+            }
+        }
+        self.undo.push_back(value);
+    }
+}
+
+impl<T> Deref for UndoSwap<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.current
+    }
+}
+
+impl<T> Debug for UndoSwap<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        <T as Debug>::fmt(&self.current, f)
+    }
+}
+
+impl<T> Display for UndoSwap<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        <T as Display>::fmt(&self.current, f)
+    }
+}
+
+impl<T> AsRef<T> for UndoSwap<T> {
+    fn as_ref(&self) -> &T {
+        &self.current
+    }
+}
+
+impl<T> std::borrow::Borrow<T> for UndoSwap<T> {
+    fn borrow(&self) -> &T {
+        &self.current
+    }
+}
@@ -0,0 +1,124 @@
+use kai::*;
+
+fn chains() -> Vec<(Vec<i32>, Vec<i32>)> {
+    vec![
+        (vec![], vec![]),
+        (vec![1, 2, 3], vec![]),
+        (vec![], vec![4, 5]),
+        (vec![1, 2, 3], vec![4, 5]),
+    ]
+}
+
+#[test]
+fn chain_if_matches_chain() {
+    for (a, b) in chains() {
+        let chained = a.clone().chain_if(true, || b.clone());
+        let chain = a.clone().into_iter().chain(b.clone());
+        assert_eq!(chain.size_hint(), chained.size_hint());
+        assert_eq!(chain.clone().count(), chained.clone().count());
+        assert_eq!(chain.clone().last(), chained.clone().last());
+        assert_eq!(
+            chain.clone().collect::<Vec<_>>(),
+            chained.clone().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            chain.clone().rev().collect::<Vec<_>>(),
+            chained.clone().rev().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            chain
+                .clone()
+                .fold(Vec::new(), |v, n| v.bind_mut(|v| v.push(n))),
+            chained
+                .clone()
+                .fold(Vec::new(), |v, n| v.bind_mut(|v| v.push(n)))
+        );
+        assert_eq!(
+            chain.clone().rfold(0, |acc, n| acc * 10 + n),
+            chained.clone().rfold(0, |acc, n| acc * 10 + n)
+        );
+        assert_eq!(
+            chain.clone().try_fold(0, |acc: i32, n| acc.checked_add(n)),
+            chained
+                .clone()
+                .try_fold(0, |acc: i32, n| acc.checked_add(n))
+        );
+    }
+}
+
+#[test]
+fn chain_if_not_chained_matches_original() {
+    for (a, b) in chains() {
+        let not_chained = a.clone().chain_if(false, || b.clone());
+        assert_eq!(a.iter().size_hint(), not_chained.size_hint());
+        assert_eq!(
+            a.iter().rev().cloned().collect::<Vec<_>>(),
+            not_chained.rev().collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn chain_if_else_matches_chain() {
+    for (a, b) in chains() {
+        for &condition in &[true, false] {
+            let c = vec![6];
+            let chained = a
+                .clone()
+                .chain_if_else(condition, || b.clone(), || c.clone());
+            let chain = a
+                .clone()
+                .into_iter()
+                .chain(if condition { b.clone() } else { c.clone() });
+            assert_eq!(chain.size_hint(), chained.size_hint());
+            assert_eq!(chain.clone().count(), chained.clone().count());
+            assert_eq!(chain.clone().last(), chained.clone().last());
+            assert_eq!(
+                chain.clone().collect::<Vec<_>>(),
+                chained.clone().collect::<Vec<_>>()
+            );
+            assert_eq!(
+                chain.clone().rev().collect::<Vec<_>>(),
+                chained.clone().rev().collect::<Vec<_>>()
+            );
+            assert_eq!(
+                chain.clone().rfold(0, |acc, n| acc * 10 + n),
+                chained.clone().rfold(0, |acc, n| acc * 10 + n)
+            );
+        }
+    }
+}
+
+#[test]
+fn size_hint_of_unbounded_chain() {
+    let chained = (0..).chain_if(true, || vec![1]);
+    assert_eq!((0..).chain(vec![1]).size_hint(), chained.size_hint());
+}
+
+#[test]
+fn size_hint_of_overflowing_chain() {
+    let chained = (0..usize::MAX).chain_if(true, || 0..2);
+    assert_eq!((0..usize::MAX).chain(0..2).size_hint(), chained.size_hint());
+}
+
+#[test]
+fn mixed_direction_iteration() {
+    let mut chained = vec![1, 2].chain_if(true, || vec![3, 4]);
+    let mut chain = vec![1, 2].into_iter().chain(vec![3, 4]);
+    assert_eq!(chain.next(), chained.next());
+    assert_eq!(chain.next_back(), chained.next_back());
+    assert_eq!(chain.clone().count(), chained.clone().count());
+    assert_eq!(chain.collect::<Vec<_>>(), chained.collect::<Vec<_>>());
+}
+
+#[test]
+fn debug() {
+    assert_eq!(
+        "Chained(IntoIter([1]), IntoIter([2]))",
+        format!("{:?}", vec![1].chain_if(true, || vec![2]))
+    );
+    assert_eq!(
+        "Else(IntoIter([1]), IntoIter([3]))",
+        format!("{:?}", vec![1].chain_if_else(false, || vec![2], || vec![3]))
+    );
+}
@@ -0,0 +1,52 @@
+use kai::*;
+
+#[test]
+#[should_panic(
+    expected = "mismatches:\n    [1]: left = 2.0, right = 2.5, absolute difference = 0.5, \
+                           relative difference = 0.2, ULP distance = 1125899906842624"
+)]
+fn reports_mismatched_numbers() {
+    assert_close!([1.0, 2.0, 3.0], [1.0, 2.5, 3.0], Tolerance::Absolute(0.1));
+}
+
+#[test]
+#[should_panic(expected = "mismatches:\n    value: left length = 2, right length = 3")]
+fn reports_mismatched_lengths() {
+    assert_close!(vec![1.0, 2.0], vec![1.0, 2.0, 3.0]);
+}
+
+#[test]
+#[should_panic(expected = "first 5 of 7 mismatches:\n    [0]: ")]
+fn reports_first_mismatches() {
+    let zeros = [0.0; 7];
+    let ones = [1.0; 7];
+    assert_close!(zeros, ones);
+}
+
+#[test]
+#[should_panic(expected = "[4]: left = 0.0, right = 1.0")]
+fn reports_up_to_the_limit() {
+    assert_close!([0.0; 7], [1.0; 7]);
+}
+
+#[test]
+#[should_panic(expected = "tolerance: Epsilon (1.1920928955078125e-7)\n")]
+fn reports_epsilon_of_the_compared_type() {
+    assert_close!(1.0f32, 1.5f32);
+}
+
+#[test]
+#[should_panic(expected = "tolerance: Epsilon (2.220446049250313e-16, 1.1920928955078125e-7)\n")]
+fn reports_epsilon_of_each_compared_type() {
+    assert_close!((1.0f64, 1.0f32), (1.5f64, 1.5f32));
+}
+
+#[test]
+fn does_not_report_past_the_limit() {
+    let message = std::panic::catch_unwind(|| assert_close!([0.0; 7], [1.0; 7]))
+        .unwrap_err()
+        .downcast::<String>()
+        .unwrap();
+    assert!(!message.contains("[5]"));
+    assert!(!message.contains("[6]"));
+}
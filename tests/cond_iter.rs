@@ -0,0 +1,140 @@
+use kai::*;
+use std::{cell::RefCell, fmt::Debug};
+
+fn inputs() -> Vec<Vec<i32>> {
+    vec![vec![], vec![1], vec![1, 2, 3, 4, 5, 6, 7]]
+}
+
+/// Check that an adapter behaves like the std iterator it should match
+fn assert_matches<A, B>(expected: A, actual: B)
+where
+    A: DoubleEndedIterator + Clone,
+    B: DoubleEndedIterator<Item = A::Item> + Clone,
+    A::Item: PartialEq + Debug,
+{
+    assert_eq!(expected.size_hint(), actual.size_hint());
+    assert_eq!(
+        expected.clone().collect::<Vec<_>>(),
+        actual.clone().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        expected.clone().rev().collect::<Vec<_>>(),
+        actual.clone().rev().collect::<Vec<_>>()
+    );
+    // Alternate between the ends until both are exhausted
+    let (mut expected, mut actual) = (expected, actual);
+    for i in 0.. {
+        let (e, a) = if i % 2 == 0 {
+            (expected.next(), actual.next())
+        } else {
+            (expected.next_back(), actual.next_back())
+        };
+        assert_eq!(e, a);
+        assert_eq!(expected.size_hint(), actual.size_hint());
+        if e.is_none() {
+            assert_eq!(None, actual.next());
+            assert_eq!(None, actual.next_back());
+            break;
+        }
+    }
+}
+
+#[test]
+fn map_if_matches_map() {
+    for v in inputs() {
+        let double = |n: i32| n * 2;
+        assert_matches(
+            v.clone().into_iter().map(double),
+            v.clone().map_if(true, double),
+        );
+        assert_matches(v.clone().into_iter(), v.clone().map_if(false, double));
+        assert_eq!(v.len(), v.clone().map_if(true, double).len());
+    }
+}
+
+#[test]
+fn filter_if_matches_filter() {
+    for v in inputs() {
+        let even = |n: &i32| n % 2 == 0;
+        assert_matches(
+            v.clone().into_iter().filter(even),
+            v.clone().filter_if(true, even),
+        );
+        assert_matches(v.clone().into_iter(), v.clone().filter_if(false, even));
+    }
+}
+
+#[test]
+fn skip_if_matches_skip() {
+    for v in inputs() {
+        for &n in &[0, 2, 10] {
+            let skip = v.clone().into_iter().skip(n);
+            assert_eq!(skip.len(), v.clone().skip_if(true, n).len());
+            assert_matches(skip, v.clone().skip_if(true, n));
+            assert_matches(v.clone().into_iter(), v.clone().skip_if(false, n));
+        }
+    }
+}
+
+#[test]
+fn take_first_if_matches_take() {
+    for v in inputs() {
+        for &n in &[0, 2, 10] {
+            let take = v.clone().into_iter().take(n);
+            assert_eq!(take.len(), v.clone().take_first_if(true, n).len());
+            assert_matches(take, v.clone().take_first_if(true, n));
+            assert_matches(v.clone().into_iter(), v.clone().take_first_if(false, n));
+        }
+    }
+}
+
+#[test]
+fn rev_if_matches_rev() {
+    for v in inputs() {
+        assert_matches(v.clone().into_iter().rev(), v.clone().rev_if(true));
+        assert_matches(v.clone().into_iter(), v.clone().rev_if(false));
+        assert_eq!(v.len(), v.clone().rev_if(true).len());
+    }
+}
+
+#[test]
+fn inspect_if_matches_inspect() {
+    for v in inputs() {
+        let ignore = |_: &i32| ();
+        assert_matches(
+            v.clone().into_iter().inspect(ignore),
+            v.clone().inspect_if(true, ignore),
+        );
+        assert_matches(v.clone().into_iter(), v.clone().inspect_if(false, ignore));
+        assert_eq!(v.len(), v.clone().inspect_if(true, ignore).len());
+
+        let expected = RefCell::new(Vec::new());
+        let actual = RefCell::new(Vec::new());
+        v.clone()
+            .into_iter()
+            .inspect(|n| expected.borrow_mut().push(*n))
+            .rev()
+            .for_each(drop);
+        v.clone()
+            .inspect_if(true, |n| actual.borrow_mut().push(*n))
+            .rev()
+            .for_each(drop);
+        assert_eq!(expected, actual);
+    }
+}
+
+#[test]
+fn adapters_are_debug() {
+    assert_eq!(
+        "Mapped(IntoIter([1]))",
+        format!("{:?}", vec![1].map_if(true, |n| n))
+    );
+    assert_eq!(
+        "NotReversed(IntoIter([1]))",
+        format!("{:?}", vec![1].rev_if(false))
+    );
+    assert_eq!(
+        "Taken(IntoIter([1]), 2)",
+        format!("{:?}", vec![1].take_first_if(true, 2))
+    );
+}
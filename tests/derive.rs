@@ -0,0 +1,236 @@
+#![cfg(feature = "derive")]
+
+use kai::{close::Mismatch, *};
+
+#[derive(Debug, Clone, PartialEq, Close)]
+struct Body {
+    name: String,
+    mass: f64,
+    position: [f32; 3],
+    #[close(abs = 1e-3)]
+    temperature: f64,
+    #[close(skip)]
+    frame: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Close)]
+struct State(#[close(approx)] Vec<Body>, #[close(eq)] f64);
+
+#[derive(Debug, Clone, PartialEq, Close)]
+struct Sample<K, V> {
+    key: K,
+    #[close(approx)]
+    values: Vec<V>,
+    weight: f64,
+}
+
+fn body() -> Body {
+    Body {
+        name: "earth".into(),
+        mass: 5.972e24,
+        position: [1.0, 2.0, 3.0],
+        temperature: 288.0,
+        frame: 0,
+    }
+}
+
+#[test]
+fn floats_are_compared_approximately() {
+    let a = body();
+    let mut b = body();
+    b.mass *= 1.0 + 1e-12;
+    b.position[1] += 1e-7;
+    assert!(!a.close(&b));
+    assert!(a.close_with(&b, Tolerance::Relative(1e-9)));
+}
+
+#[test]
+fn other_fields_use_partial_eq() {
+    let a = body();
+    let mut b = body();
+    b.name = "mars".into();
+    assert!(!a.close_with(&b, Tolerance::Relative(1.0)));
+}
+
+#[test]
+fn field_tolerance_overrides_tolerance() {
+    let a = body();
+    let mut b = body();
+    b.temperature += 5e-4;
+    assert!(a.close(&b));
+    b.temperature += 1e-2;
+    assert!(!a.close_with(&b, Tolerance::Relative(1.0)));
+}
+
+#[test]
+fn skipped_fields_are_ignored() {
+    let a = body();
+    let mut b = body();
+    b.frame = 10;
+    assert!(a.close(&b));
+}
+
+#[test]
+fn tuple_structs_and_nesting() {
+    let a = State(vec![body(), body()], 1.0);
+    let mut b = a.clone();
+    b.0[1].position[2] = 3.5;
+    b.1 = 1.0 + 1e-15;
+    let mut mismatches = Vec::new();
+    a.mismatches(&b, Tolerance::Epsilon, "", &mut mismatches);
+    assert_eq!(2, mismatches.len());
+    assert_eq!(".0[1].position[2]", mismatches[0].path());
+    assert_eq!(Mismatch::Values { path: ".1".into() }, mismatches[1]);
+}
+
+#[test]
+fn generic_structs() {
+    let a = Sample {
+        key: "x",
+        values: vec![1.0f32, 2.0],
+        weight: 0.5,
+    };
+    let mut b = a.clone();
+    b.values[1] += 1e-7;
+    assert!(a.close(&b));
+    b.key = "y";
+    assert!(!a.close(&b));
+}
+
+#[test]
+fn works_with_assert_close() {
+    let a = body();
+    let mut b = body();
+    b.position[0] += 1e-9;
+    assert_close!(a, b);
+}
+
+#[derive(Debug, Clone, PartialEq, Variants)]
+enum Shape {
+    Circle(f64),
+    Rect(f64, f64),
+    Polygon { sides: u32, length: f64 },
+    Empty,
+    HTTPPoint(i32),
+}
+
+#[test]
+fn variant_predicates() {
+    assert!(Shape::Circle(1.0).is_circle());
+    assert!(!Shape::Circle(1.0).is_rect());
+    assert!(Shape::Empty.is_empty());
+    assert!(Shape::HTTPPoint(0).is_http_point());
+}
+
+#[test]
+fn variant_accessors() {
+    let mut polygon = Shape::Polygon {
+        sides: 3,
+        length: 2.0,
+    };
+    assert_eq!(Some((&3, &2.0)), polygon.as_polygon());
+    assert_eq!(None, polygon.as_circle());
+    if let Some((sides, _)) = polygon.as_polygon_mut() {
+        *sides = 4;
+    }
+    assert_eq!(Ok((4, 2.0)), polygon.clone().into_polygon());
+    assert_eq!(Err(Shape::Empty), Shape::Empty.into_polygon());
+    let shapes = [
+        Shape::Circle(1.0),
+        Shape::Rect(1.0, 2.0),
+        Shape::Circle(3.0),
+    ];
+    let radii: Vec<f64> = shapes
+        .iter()
+        .filter_map(Shape::as_circle)
+        .copied()
+        .collect();
+    assert_eq!(vec![1.0, 3.0], radii);
+}
+
+#[test]
+fn variant_try_from() {
+    use std::convert::TryFrom;
+    assert_eq!(Ok(2.0), f64::try_from(Shape::Circle(2.0)));
+    assert_eq!(Err(Shape::Empty), f64::try_from(Shape::Empty));
+    assert_eq!(
+        Ok((1.0, 2.0)),
+        <(f64, f64)>::try_from(Shape::Rect(1.0, 2.0))
+    );
+}
+
+#[derive(Debug, PartialEq, Variants)]
+enum Tree<T> {
+    Leaf(T),
+    Branch(Vec<Tree<T>>),
+    Count(usize),
+}
+
+#[test]
+fn generic_variants() {
+    use std::convert::TryFrom;
+    let mut tree = Tree::Branch(vec![Tree::Leaf(1), Tree::Leaf(2)]);
+    if let Some(children) = tree.as_branch_mut() {
+        children.push(Tree::Leaf(3));
+    }
+    let leaves: Vec<i32> = tree
+        .into_branch()
+        .unwrap()
+        .into_iter()
+        .filter_map(|child| child.into_leaf().ok())
+        .collect();
+    assert_eq!(vec![1, 2, 3], leaves);
+    assert_eq!(Ok(4), usize::try_from(Tree::<i32>::Count(4)));
+}
+
+#[derive(Debug, PartialEq, Variants)]
+#[variants(no_try_from)]
+enum Temperature {
+    Celsius(f64),
+    Kelvin(u32),
+}
+
+// Would conflict with a derived `TryFrom<Temperature> for f64`
+impl From<Temperature> for f64 {
+    fn from(temperature: Temperature) -> Self {
+        match temperature {
+            Temperature::Celsius(c) => c,
+            Temperature::Kelvin(k) => f64::from(k) - 273.15,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Variants)]
+enum Token {
+    #[variants(no_try_from)]
+    Word(String),
+    Number(i64),
+}
+
+impl std::convert::TryFrom<Token> for String {
+    type Error = Token;
+    fn try_from(token: Token) -> Result<Self, Self::Error> {
+        match token {
+            Token::Word(word) => Ok(word),
+            Token::Number(n) => Ok(n.to_string()),
+        }
+    }
+}
+
+#[test]
+fn try_from_opt_out() {
+    use std::convert::TryFrom;
+    assert_eq!(Some(&300), Temperature::Kelvin(300).as_kelvin());
+    assert_close!(
+        26.85,
+        f64::from(Temperature::Kelvin(300)),
+        Tolerance::Absolute(1e-9)
+    );
+    assert_eq!(1.5, f64::from(Temperature::Celsius(1.5)));
+    assert_eq!(
+        Ok("a".to_string()),
+        String::try_from(Token::Word("a".into()))
+    );
+    assert_eq!(Ok("12".to_string()), String::try_from(Token::Number(12)));
+    assert_eq!(Ok(12), i64::try_from(Token::Number(12)));
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "serde")]
+
+use kai::{thread::ThreadStatus, *};
+
+#[test]
+fn swap_round_trip() {
+    let swap = Swap::new(vec![1, 2, 3]);
+    let json = serde_json::to_string(&swap).unwrap();
+    assert_eq!("[1,2,3]", json);
+    let de: Swap<Vec<i32>> = serde_json::from_str(&json).unwrap();
+    assert_eq!(swap, de);
+}
+
+#[test]
+fn swap_in_struct_round_trip() {
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Config {
+        name: Swap<String>,
+        retries: Swap<u8>,
+    }
+    let config = Config {
+        name: String::from("kai").into(),
+        retries: 3.into(),
+    };
+    let json = serde_json::to_string(&config).unwrap();
+    assert_eq!(r#"{"name":"kai","retries":3}"#, json);
+    let de: Config = serde_json::from_str(&json).unwrap();
+    assert_eq!(config, de);
+}
+
+#[test]
+fn thread_status_round_trip() {
+    for &status in &[
+        ThreadStatus::Running,
+        ThreadStatus::Finished,
+        ThreadStatus::Panicked,
+    ] {
+        let json = serde_json::to_string(&status).unwrap();
+        let de: ThreadStatus = serde_json::from_str(&json).unwrap();
+        assert_eq!(status, de);
+    }
+}
+
+#[test]
+fn adapter_serializes_parsed_value() {
+    let mut s = String::from("42");
+    let adapter = Adapter::<i32>::from(&mut s).unwrap();
+    assert_eq!("42", serde_json::to_string(&adapter).unwrap());
+}